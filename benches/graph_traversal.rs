@@ -0,0 +1,57 @@
+//! Searches on the linked adjacency lists of `Digraph` against the flat arrays of `CsrGraph`.
+#![feature(test)]
+
+extern crate test;
+
+use adivon::graph::{CsrGraph, Digraph, StronglyConnectedComponents};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use test::Bencher;
+
+const V: usize = 100_000;
+const E: usize = 1_000_000;
+
+fn random_digraph() -> Digraph {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut g = Digraph::new(V);
+    for _ in 0..E {
+        g.add_edge(rng.gen_range(0, V), rng.gen_range(0, V));
+    }
+    g
+}
+
+#[bench]
+fn bench_digraph_bfs(b: &mut Bencher) {
+    let g = random_digraph();
+    b.iter(|| g.bfs(0).has_path_to(V - 1));
+}
+
+#[bench]
+fn bench_csr_bfs(b: &mut Bencher) {
+    let g = CsrGraph::from(&random_digraph());
+    b.iter(|| g.bfs(0).has_path_to(V - 1));
+}
+
+#[bench]
+fn bench_digraph_dfs(b: &mut Bencher) {
+    let g = random_digraph();
+    b.iter(|| g.dfs(0).has_path_to(V - 1));
+}
+
+#[bench]
+fn bench_csr_dfs(b: &mut Bencher) {
+    let g = CsrGraph::from(&random_digraph());
+    b.iter(|| g.dfs(0).has_path_to(V - 1));
+}
+
+#[bench]
+fn bench_digraph_tarjan_scc(b: &mut Bencher) {
+    let g = random_digraph();
+    b.iter(|| g.tarjan_scc().id(0));
+}
+
+#[bench]
+fn bench_csr_tarjan_scc(b: &mut Bencher) {
+    let g = CsrGraph::from(&random_digraph());
+    b.iter(|| g.tarjan_scc().id(0));
+}
@@ -0,0 +1,110 @@
+//! Separate chaining in `HashST` against open addressing in `LinearProbingHashST`, with small integer keys.
+#![feature(test)]
+
+extern crate test;
+
+use adivon::hashst::{HashST, LinearProbingHashST};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use test::{black_box, Bencher};
+
+const N: usize = 100_000;
+
+fn random_keys() -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(42);
+    (0..N).map(|_| rng.gen()).collect()
+}
+
+#[bench]
+fn bench_chaining_put(b: &mut Bencher) {
+    let keys = random_keys();
+    b.iter(|| {
+        let mut st = HashST::new();
+        for &k in &keys {
+            st.put(k, k);
+        }
+        st.size()
+    });
+}
+
+#[bench]
+fn bench_linear_probing_put(b: &mut Bencher) {
+    let keys = random_keys();
+    b.iter(|| {
+        let mut st = LinearProbingHashST::new();
+        for &k in &keys {
+            st.put(k, k);
+        }
+        st.size()
+    });
+}
+
+#[bench]
+fn bench_chaining_get(b: &mut Bencher) {
+    let keys = random_keys();
+    let mut st = HashST::new();
+    for &k in &keys {
+        st.put(k, k);
+    }
+    b.iter(|| keys.iter().filter(|&&k| st.get(black_box(k)).is_some()).count());
+}
+
+#[bench]
+fn bench_linear_probing_get(b: &mut Bencher) {
+    let keys = random_keys();
+    let mut st = LinearProbingHashST::new();
+    for &k in &keys {
+        st.put(k, k);
+    }
+    b.iter(|| keys.iter().filter(|&&k| st.get(black_box(k)).is_some()).count());
+}
+
+#[bench]
+fn bench_chaining_get_missing(b: &mut Bencher) {
+    let keys = random_keys();
+    let mut st = HashST::new();
+    for &k in &keys {
+        st.put(k, k);
+    }
+    b.iter(|| keys.iter().filter(|&&k| st.get(black_box(!k)).is_some()).count());
+}
+
+#[bench]
+fn bench_linear_probing_get_missing(b: &mut Bencher) {
+    let keys = random_keys();
+    let mut st = LinearProbingHashST::new();
+    for &k in &keys {
+        st.put(k, k);
+    }
+    b.iter(|| keys.iter().filter(|&&k| st.get(black_box(!k)).is_some()).count());
+}
+
+#[bench]
+fn bench_chaining_put_delete(b: &mut Bencher) {
+    let keys = random_keys();
+    b.iter(|| {
+        let mut st = HashST::new();
+        for &k in &keys {
+            st.put(k, k);
+        }
+        for &k in &keys {
+            st.delete(&k);
+        }
+        st.size()
+    });
+}
+
+#[bench]
+fn bench_linear_probing_put_delete(b: &mut Bencher) {
+    let keys = random_keys();
+    b.iter(|| {
+        let mut st = LinearProbingHashST::new();
+        for &k in &keys {
+            st.put(k, k);
+        }
+        for &k in &keys {
+            st.delete(&k);
+        }
+        st.size()
+    });
+}
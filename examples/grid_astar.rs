@@ -0,0 +1,72 @@
+extern crate adivon;
+
+use adivon::graph::implicit::{astar, bidirectional_bfs};
+use adivon::primitive::Point2D;
+
+const MAP: [&str; 9] = [
+    "S.........#.........",
+    ".########.#.######..",
+    ".#......#.#......#..",
+    ".#.####.#.####.#.#..",
+    ".#.#..#.#....#.#.#..",
+    ".#.#..#.######.#.#..",
+    ".#.#..#........#.#..",
+    "...#..##########.#..",
+    "...#.............#.G",
+];
+
+fn find(c: u8) -> (usize, usize) {
+    for (y, row) in MAP.iter().enumerate() {
+        if let Some(x) = row.bytes().position(|b| b == c) {
+            return (x, y);
+        }
+    }
+    panic!("no {} on the map", c as char)
+}
+
+fn point((x, y): (usize, usize)) -> Point2D {
+    Point2D::new(x as f64, y as f64)
+}
+
+fn free(x: isize, y: isize) -> bool {
+    y >= 0 &&
+        (y as usize) < MAP.len() &&
+        x >= 0 &&
+        MAP[y as usize].as_bytes().get(x as usize).map_or(false, |&b| b != b'#')
+}
+
+/// Moves to the eight surrounding free cells, without cutting corners, costing the distance travelled.
+fn moves((x, y): (usize, usize)) -> Vec<((usize, usize), f64)> {
+    let mut next = Vec::new();
+    for dy in -1..2 {
+        for dx in -1..2 {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if (dx, dy) != (0, 0) && free(nx, ny) && free(nx, y as isize) && free(x as isize, ny) {
+                let to = (nx as usize, ny as usize);
+                next.push((to, point((x, y)).distance_to(point(to))));
+            }
+        }
+    }
+    next
+}
+
+fn main() {
+    let (start, goal) = (find(b'S'), find(b'G'));
+
+    // straight-line distance never overestimates, so the path found is a shortest one
+    let (path, cost) = astar(start, |&p| moves(p), |&p| point(p).distance_to(point(goal)), |&p| p == goal)
+        .expect("goal is unreachable");
+
+    let mut rows = MAP.iter().map(|row| row.as_bytes().to_vec()).collect::<Vec<Vec<u8>>>();
+    for &(x, y) in &path[1..path.len() - 1] {
+        rows[y][x] = b'*';
+    }
+    for row in rows {
+        println!("{}", String::from_utf8(row).unwrap());
+    }
+    println!("A*: {} moves, length {:.3}", path.len() - 1, cost);
+
+    let cells = |&p: &(usize, usize)| moves(p).into_iter().map(|m| m.0);
+    let (_, steps) = bidirectional_bfs(start, goal, cells, cells).expect("goal is unreachable");
+    println!("bidirectional BFS: {} moves", steps);
+}
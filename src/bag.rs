@@ -64,6 +64,35 @@ impl<T> Bag<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Remove and return one item satisfying `f`.
+    pub fn remove_first<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Option<T> {
+        let mut link = &mut self.s;
+        while link.is_some() {
+            if f(&link.as_ref().unwrap().val) {
+                let Node { val, next } = *link.take().unwrap();
+                *link = next;
+                self.n -= 1;
+                return Some(val);
+            }
+            link = &mut link.as_mut().unwrap().next;
+        }
+        None
+    }
+
+    /// Keep only the items satisfying `f`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut link = &mut self.s;
+        while link.is_some() {
+            if f(&link.as_ref().unwrap().val) {
+                link = &mut link.as_mut().unwrap().next;
+            } else {
+                let next = link.as_mut().unwrap().next.take();
+                *link = next;
+                self.n -= 1;
+            }
+        }
+    }
 }
 
 pub struct Iter<'a, T>
@@ -100,6 +129,37 @@ impl<'a, T> ExactSizeIterator for Iter<'a, T> {
     }
 }
 
+pub struct IterMut<'a, T>
+where
+    T: 'a,
+{
+    node: Option<&'a mut Box<Node<T>>>,
+    nitem: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.node.take().map(|node| {
+            let node = &mut **node;
+            self.node = node.next.as_mut();
+            self.nitem -= 1;
+            &mut node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nitem, Some(self.nitem))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.nitem
+    }
+}
+
 impl<T> Bag<T> {
     pub fn iter(&self) -> Iter<T> {
         Iter {
@@ -107,6 +167,13 @@ impl<T> Bag<T> {
             nitem: self.n,
         }
     }
+
+    pub fn iter_mut(&mut self) -> IterMut<T> {
+        IterMut {
+            node: self.s.as_mut(),
+            nitem: self.n,
+        }
+    }
 }
 
 #[test]
@@ -147,3 +214,28 @@ fn test_bag_clone() {
 
     assert_eq!(t.len(), 3);
 }
+
+#[test]
+fn test_bag_remove() {
+    let mut s = Bag::new();
+    for i in 0..10 {
+        s.add(i);
+    }
+
+    assert_eq!(Some(9), s.remove_first(|&i| i > 5));
+    assert_eq!(Some(0), s.remove_first(|&i| i == 0));
+    assert_eq!(None, s.remove_first(|&i| i == 0));
+    assert_eq!(8, s.len());
+
+    s.retain(|&i| i % 2 == 0);
+    assert_eq!(vec![8, 6, 4, 2], s.iter().cloned().collect::<Vec<i32>>());
+
+    for i in s.iter_mut() {
+        *i *= 10;
+    }
+    assert_eq!(vec![80, 60, 40, 20], s.iter().cloned().collect::<Vec<i32>>());
+
+    s.retain(|_| false);
+    assert!(s.is_empty());
+    assert_eq!(0, s.iter().count());
+}
@@ -0,0 +1,255 @@
+use super::super::priority_queue::IndexMinPQ;
+use super::super::stack::Stack;
+use super::weighted_directed::{DirectedEdge, EdgeWeightedDigraph};
+use super::Digraph;
+use std::f64;
+use std::iter;
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// Shortest paths between every pair of vertices: for each source, a tree of paths stored as the last edge of the
+/// path to every vertex.
+pub struct AllPairsShortestPaths {
+    dist: Vec<Vec<f64>>,
+    edge_to: Vec<Vec<Option<DirectedEdge>>>,
+}
+
+impl AllPairsShortestPaths {
+    fn new(n: usize) -> AllPairsShortestPaths {
+        let mut dist = iter::repeat(vec![f64::INFINITY; n]).take(n).collect::<Vec<Vec<f64>>>();
+        for (v, row) in dist.iter_mut().enumerate() {
+            row[v] = 0.0;
+        }
+        AllPairsShortestPaths {
+            dist: dist,
+            edge_to: iter::repeat(vec![None; n]).take(n).collect(),
+        }
+    }
+
+    /// Number of vertices.
+    pub fn v(&self) -> usize {
+        self.dist.len()
+    }
+
+    /// Length of a shortest path from `u` to `v`, infinite if there is none.
+    pub fn dist(&self, u: usize, v: usize) -> f64 {
+        self.dist[u][v]
+    }
+
+    pub fn has_path(&self, u: usize, v: usize) -> bool {
+        self.dist[u][v] < f64::INFINITY
+    }
+
+    /// Edges of a shortest path from `u` to `v`, empty if `u == v`.
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<DirectedEdge>> {
+        if self.has_path(u, v) {
+            let mut path = Stack::new();
+            let mut x = v;
+            while x != u {
+                let e = self.edge_to[u][x].unwrap();
+                path.push(e);
+                x = e.from();
+            }
+            Some(path.into_iter().collect())
+        } else {
+            None
+        }
+    }
+
+    /// The distances as a `V` by `V` matrix, row `u` holding the distances from `u`.
+    pub fn matrix(&self) -> &[Vec<f64>] {
+        &self.dist
+    }
+}
+
+/// All-pairs shortest paths by the Floyd-Warshall algorithm, in `O(V^3)` time whatever the number of edges.
+/// Edge weights may be negative.
+///
+/// Returns the edges of a negative cycle as the error, if there is one.
+pub fn floyd_warshall(graph: &EdgeWeightedDigraph) -> Result<AllPairsShortestPaths, Vec<DirectedEdge>> {
+    let n = graph.v();
+    let mut sp = AllPairsShortestPaths::new(n);
+    for e in graph.all_edges() {
+        if e.weight() < sp.dist[e.from()][e.to()] {
+            sp.dist[e.from()][e.to()] = e.weight();
+            sp.edge_to[e.from()][e.to()] = Some(e);
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            if sp.edge_to[i][k].is_none() && i != k {
+                continue;
+            }
+            for j in 0..n {
+                if sp.dist[i][k] + sp.dist[k][j] < sp.dist[i][j] {
+                    sp.dist[i][j] = sp.dist[i][k] + sp.dist[k][j];
+                    sp.edge_to[i][j] = sp.edge_to[k][j];
+                }
+            }
+            if sp.dist[i][i] < 0.0 {
+                return Err(negative_cycle(&sp, i));
+            }
+        }
+    }
+    Ok(sp)
+}
+
+/// Once `dist[v][v]` is negative, the last edges of the paths from `v` close a negative cycle.
+fn negative_cycle(sp: &AllPairsShortestPaths, v: usize) -> Vec<DirectedEdge> {
+    let mut parents = Digraph::new(sp.v());
+    for e in sp.edge_to[v].iter().filter_map(|e| *e) {
+        parents.add_edge(e.from(), e.to());
+    }
+    let cycle = parents.directed_cycle().cycle().unwrap().to_vec();
+    cycle.windows(2).map(|w| sp.edge_to[v][w[1]].unwrap()).collect()
+}
+
+/// All-pairs shortest paths by Johnson's algorithm, in `O(V E log V)` time, much faster than Floyd-Warshall on
+/// sparse digraphs. Edge weights may be negative.
+///
+/// Bellman-Ford from an extra vertex pointing to every other one finds potentials `h` that make every reduced
+/// weight `w(u, v) + h(u) - h(v)` nonnegative without changing which paths are shortest, then Dijkstra's algorithm
+/// runs from each vertex on the reduced weights. Returns the edges of a negative cycle as the error, if there is
+/// one.
+pub fn johnson(graph: &EdgeWeightedDigraph) -> Result<AllPairsShortestPaths, Vec<DirectedEdge>> {
+    let n = graph.v();
+    let mut augmented = EdgeWeightedDigraph::new(n + 1);
+    for e in graph.all_edges() {
+        augmented.add_edge(e);
+    }
+    for v in 0..n {
+        augmented.add_edge(DirectedEdge::new(n, v, 0.0));
+    }
+    let potentials = augmented.bellman_ford(n)?;
+    let h = (0..n).map(|v| potentials.dist_to(v)).collect::<Vec<f64>>();
+
+    let mut sp = AllPairsShortestPaths::new(n);
+    // distances under the reduced weights order the queue, the true lengths are summed alongside
+    let mut reduced = vec![f64::INFINITY; n];
+    for s in 0..n {
+        for d in reduced.iter_mut() {
+            *d = f64::INFINITY;
+        }
+        reduced[s] = 0.0;
+        let mut pq = IndexMinPQ::with_capacity(n);
+        pq.insert(s, 0.0);
+        while let Some(v) = pq.del_min() {
+            for e in graph.adj(v) {
+                let w = e.to();
+                // rounding may leave a reduced weight slightly below zero
+                let weight = (e.weight() + h[v] - h[w]).max(0.0);
+                if reduced[w] > reduced[v] + weight {
+                    reduced[w] = reduced[v] + weight;
+                    sp.dist[s][w] = sp.dist[s][v] + e.weight();
+                    sp.edge_to[s][w] = Some(*e);
+                    if pq.contains(w) {
+                        pq.decrease_key(w, reduced[w]);
+                    } else {
+                        pq.insert(w, reduced[w]);
+                    }
+                }
+            }
+        }
+    }
+    Ok(sp)
+}
+
+#[cfg(test)]
+fn digraph_from_edges(v: usize, edges: &[(usize, usize, f64)]) -> EdgeWeightedDigraph {
+    let mut g = EdgeWeightedDigraph::new(v);
+    for &(v, w, weight) in edges {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+    g
+}
+
+#[cfg(test)]
+fn assert_all_pairs(g: &EdgeWeightedDigraph, sp: &AllPairsShortestPaths) {
+    for u in 0..g.v() {
+        let single = g.bellman_ford(u).unwrap();
+        for v in 0..g.v() {
+            assert_eq!(single.has_path_to(v), sp.has_path(u, v));
+            if let Some(path) = sp.path(u, v) {
+                let len = path.iter().map(|e| e.weight()).sum::<f64>();
+                assert!((single.dist_to(v) - sp.dist(u, v)).abs() < 1e-9);
+                assert!((len - sp.dist(u, v)).abs() < 1e-9);
+                assert!(path.windows(2).all(|p| p[0].to() == p[1].from()));
+                assert!(path.first().map_or(u, |e| e.from()) == u && path.last().map_or(v, |e| e.to()) == v);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_all_pairs_shortest_paths() {
+    let mut edges = vec![
+        (4, 5, 0.35),
+        (5, 4, 0.35),
+        (4, 7, 0.37),
+        (5, 7, 0.28),
+        (7, 5, 0.28),
+        (5, 1, 0.32),
+        (0, 4, 0.38),
+        (0, 2, 0.26),
+        (7, 3, 0.39),
+        (1, 3, 0.29),
+        (2, 7, 0.34),
+        (6, 2, -1.20),
+        (3, 6, 0.52),
+        (6, 0, -1.40),
+        (6, 4, -1.25),
+    ];
+    let g = digraph_from_edges(9, &edges);
+    let fw = g.floyd_warshall().unwrap();
+    let jo = g.johnson().unwrap();
+    assert_all_pairs(&g, &fw);
+    assert_all_pairs(&g, &jo);
+    assert_eq!(9, fw.v());
+    assert!((fw.dist(0, 6) - 1.51).abs() < 1e-9);
+    assert!((jo.dist(3, 4) + 0.73).abs() < 1e-9);
+    assert_eq!(Some(vec![]), fw.path(8, 8));
+    assert_eq!(None, jo.path(0, 8));
+    assert_eq!(f64::INFINITY, fw.matrix()[0][8]);
+    assert_eq!(0.0, jo.matrix()[8][8]);
+
+    // 4 -> 5 -> 4 is a negative cycle
+    edges[1] = (5, 4, -0.66);
+    let g = digraph_from_edges(9, &edges);
+    for cycle in vec![g.floyd_warshall().err().unwrap(), g.johnson().err().unwrap()] {
+        assert!(cycle.iter().map(|e| e.weight()).sum::<f64>() < 0.0);
+        assert!(cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .all(|(a, b)| a.to() == b.from()));
+    }
+
+    let empty = EdgeWeightedDigraph::new(0);
+    assert_eq!(0, empty.floyd_warshall().unwrap().v());
+    assert_eq!(0, empty.johnson().unwrap().v());
+}
+
+#[test]
+fn quicktest_all_pairs_shortest_paths() {
+    fn prop(n: u8, edges: Vec<(u8, u8, i8)>) -> bool {
+        let n = n as usize % 12 + 1;
+        let mut g = EdgeWeightedDigraph::new(n);
+        for (v, w, weight) in edges {
+            // mostly nonnegative weights, so that not every digraph has a negative cycle
+            g.add_edge(DirectedEdge::new(v as usize % n, w as usize % n, weight as f64 / 16.0 + 2.0));
+        }
+        match (g.floyd_warshall(), g.johnson()) {
+            (Ok(fw), Ok(jo)) => {
+                assert_all_pairs(&g, &fw);
+                assert_all_pairs(&g, &jo);
+                true
+            }
+            (Err(a), Err(b)) => {
+                a.iter().map(|e| e.weight()).sum::<f64>() < 0.0 && b.iter().map(|e| e.weight()).sum::<f64>() < 0.0
+            }
+            _ => false,
+        }
+    }
+    quickcheck(prop as fn(u8, Vec<(u8, u8, i8)>) -> bool);
+}
@@ -0,0 +1,160 @@
+use super::directed::{GabowSCC, TarjanSCC};
+use super::traversal::SearchPaths;
+use super::view::GraphView;
+use super::{Digraph, Graph};
+use std::iter;
+use std::slice;
+
+/// An immutable graph in compressed sparse row form: the neighbours of `v` are `targets[offsets[v]..offsets[v + 1]]`.
+///
+/// Frozen from a `Graph` or a `Digraph`, it keeps their adjacency order, so that searches visit vertices in the
+/// same order, but lays the lists out in two flat arrays instead of linked nodes.
+#[derive(Clone, Debug)]
+pub struct CsrGraph {
+    directed: bool,
+    e: usize,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+impl CsrGraph {
+    fn build<G: GraphView>(g: &G, directed: bool) -> CsrGraph {
+        let v = g.vertex_count();
+        let mut offsets = Vec::with_capacity(v + 1);
+        let mut targets = Vec::new();
+        offsets.push(0);
+        for s in 0..v {
+            targets.extend(g.neighbors(s));
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            directed: directed,
+            e: g.edge_count(),
+            offsets: offsets,
+            targets: targets,
+        }
+    }
+
+    pub fn from_graph(g: &Graph) -> CsrGraph {
+        CsrGraph::build(g, false)
+    }
+
+    pub fn from_digraph(g: &Digraph) -> CsrGraph {
+        CsrGraph::build(g, true)
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn vertices(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn edges(&self) -> usize {
+        self.e
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(v < self.vertices(), "vertex is not between 0 and {}", self.vertices() as isize - 1)
+    }
+
+    /// Degree of `v`, or its outdegree if the graph is directed.
+    pub fn degree(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    pub fn adj(&self, v: usize) -> &[usize] {
+        self.validate_vertex(v);
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    pub fn dfs(&self, s: usize) -> SearchPaths<CsrGraph> {
+        SearchPaths::depth_first(self, iter::once(s))
+    }
+
+    pub fn dfs_multi_source<T: IntoIterator<Item = usize>>(&self, s: T) -> SearchPaths<CsrGraph> {
+        SearchPaths::depth_first(self, s)
+    }
+
+    pub fn bfs(&self, s: usize) -> SearchPaths<CsrGraph> {
+        SearchPaths::breadth_first(self, iter::once(s))
+    }
+
+    /// Strongly-connected components, or connected components if the graph is undirected.
+    pub fn tarjan_scc(&self) -> TarjanSCC<CsrGraph> {
+        TarjanSCC::new(self)
+    }
+
+    pub fn gabow_scc(&self) -> GabowSCC<CsrGraph> {
+        GabowSCC::new(self)
+    }
+}
+
+impl<'a> From<&'a Graph> for CsrGraph {
+    fn from(g: &'a Graph) -> CsrGraph {
+        CsrGraph::from_graph(g)
+    }
+}
+
+impl<'a> From<&'a Digraph> for CsrGraph {
+    fn from(g: &'a Digraph) -> CsrGraph {
+        CsrGraph::from_digraph(g)
+    }
+}
+
+impl GraphView for CsrGraph {
+    type Neighbors<'a> = iter::Cloned<slice::Iter<'a, usize>>;
+
+    fn vertex_count(&self) -> usize {
+        self.vertices()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.e
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.adj(v).iter().cloned()
+    }
+}
+
+#[cfg(test)]
+use super::StronglyConnectedComponents;
+
+#[test]
+fn test_csr_graph() {
+    let g: Digraph = "13 22\n4 2\n2 3\n3 2\n6 0\n0 1\n2 0\n11 12\n12 9\n9 10\n9 11\n7 9\n10 12\n11 4\n4 3\n3 5\n6 8\n\
+                      8 6\n5 4\n0 5\n6 4\n6 9\n7 6"
+        .parse()
+        .unwrap();
+    let csr = CsrGraph::from(&g);
+    assert!(csr.is_directed());
+    assert_eq!((13, 22), (csr.vertices(), csr.edges()));
+    for v in 0..g.v() {
+        assert_eq!(g.adj(v), csr.adj(v));
+        assert_eq!(g.outdegree(v), csr.degree(v));
+    }
+    assert_eq!(g.bfs(7).path_to(1), csr.bfs(7).path_to(1));
+    assert_eq!(g.dfs(0).path_to(3), csr.dfs(0).path_to(3));
+    assert!(!csr.dfs_multi_source(vec![1, 8]).has_path_to(7));
+
+    let scc = csr.tarjan_scc();
+    assert_eq!(5, scc.count());
+    assert_eq!(g.tarjan_scc().components(), scc.components());
+    assert_eq!(g.kosaraju_sharir_scc().count(), csr.gabow_scc().count());
+    assert_eq!(g.tarjan_scc().condensation().e(), scc.condensation().e());
+
+    let g: Graph = "6 4\n0 1\n1 2\n3 4\n5 5".parse().unwrap();
+    let csr = CsrGraph::from_graph(&g);
+    assert!(!csr.is_directed());
+    assert_eq!((6, 4), (csr.vertices(), csr.edges()));
+    assert_eq!(2, csr.degree(5));
+    assert_eq!(Some(vec![0, 1, 2]), csr.bfs(0).path_to(2));
+    assert_eq!(3, csr.tarjan_scc().count());
+
+    let empty = CsrGraph::from_digraph(&Digraph::new(0));
+    assert_eq!(0, empty.vertices());
+    assert_eq!(0, empty.tarjan_scc().count());
+}
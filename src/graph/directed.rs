@@ -1,7 +1,17 @@
+use super::super::bag;
 use super::super::bag::Bag;
-use super::super::queue::Queue;
+use super::super::priority_queue::MinPQ;
 use super::super::stack;
 use super::super::stack::Stack;
+use super::euler;
+pub use super::euler::EulerianError;
+use super::hamiltonian;
+use super::reachability;
+pub use super::reachability::{CondensedClosure, TransitiveClosure};
+pub use super::traversal::{DepthFirstOrder, SearchPaths};
+use super::view::GraphView;
+pub use super::SearchSource;
+use std::cmp;
 use std::iter;
 
 #[derive(Clone, Debug)]
@@ -45,6 +55,55 @@ impl Digraph {
         self.adj[v].len()
     }
 
+    /// Add a vertex without edges, returning its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Bag::new());
+        self.v += 1;
+        self.v - 1
+    }
+
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.adj[v].iter().any(|&x| x == w)
+    }
+
+    /// Remove one of the edges from `v` to `w`, returning whether there was one.
+    pub fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+
+        if self.adj[v].remove_first(|&x| x == w).is_none() {
+            return false;
+        }
+        self.e -= 1;
+        true
+    }
+
+    /// Remove `v` and the edges from and to it. As with `Vec::swap_remove`, the last vertex takes over the index
+    /// `v`. Takes time proportional to V + E, since the edges pointing to `v` are found by scanning all
+    /// adjacency lists.
+    pub fn remove_vertex(&mut self, v: usize) {
+        self.validate_vertex(v);
+        let last = self.v - 1;
+
+        let removed = self.adj.swap_remove(v);
+        self.v -= 1;
+        self.e -= removed.len();
+        for adj in self.adj.iter_mut() {
+            let outdegree = adj.len();
+            adj.retain(|&w| w != v);
+            self.e -= outdegree - adj.len();
+            if v != last {
+                for w in adj.iter_mut() {
+                    if *w == last {
+                        *w = v;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn number_of_self_loops(&self) -> usize {
         let mut count = 0;
         for v in 0..self.v() {
@@ -78,6 +137,23 @@ impl Digraph {
         self.adj[v].iter().cloned().collect()
     }
 
+    pub fn all_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::with_capacity(self.e);
+        for (v, adj) in self.adj.iter().enumerate() {
+            edges.extend(adj.iter().map(|&w| (v, w)));
+        }
+        edges
+    }
+
+    /// The number of edges from each vertex to each vertex.
+    pub fn adjacency_matrix(&self) -> Vec<Vec<usize>> {
+        let mut matrix = vec![vec![0; self.v]; self.v];
+        for (v, w) in self.all_edges() {
+            matrix[v][w] += 1;
+        }
+        matrix
+    }
+
     pub fn reverse(&self) -> Digraph {
         let v = self.v;
         let mut adj = iter::repeat(Bag::new()).take(v).collect::<Vec<Bag<usize>>>();
@@ -93,164 +169,284 @@ impl Digraph {
         }
     }
 
-    pub fn dfs(&self, s: usize) -> SearchPaths {
-        let mut path = SearchPaths::new(self, SearchSource::Single(s));
-        path.dfs();
-        path
+    pub fn dfs(&self, s: usize) -> SearchPaths<Digraph> {
+        SearchPaths::depth_first(self, iter::once(s))
     }
 
-    pub fn dfs_multi_source<T: IntoIterator<Item = usize>>(&self, s: T) -> SearchPaths {
-        let mut path = SearchPaths::new(self, SearchSource::Multi(s.into_iter().collect()));
-        path.dfs();
-        path
+    pub fn dfs_multi_source<T: IntoIterator<Item = usize>>(&self, s: T) -> SearchPaths<Digraph> {
+        SearchPaths::depth_first(self, s)
     }
 
-    pub fn bfs(&self, s: usize) -> SearchPaths {
-        let mut path = SearchPaths::new(self, SearchSource::Single(s));
-        path.bfs();
-        path
+    pub fn bfs(&self, s: usize) -> SearchPaths<Digraph> {
+        SearchPaths::breadth_first(self, iter::once(s))
     }
 
     pub fn reverse_dfs_postorder(&self) -> stack::IntoIter<usize> {
-        let mut dfo = DepthFirstOrder::new(self);
-        dfo.init();
-        dfo.reverse_post.into_iter()
+        let mut reverse_post = Stack::new();
+        for &v in DepthFirstOrder::new(self).post() {
+            reverse_post.push(v);
+        }
+        reverse_post.into_iter()
     }
 
     pub fn kosaraju_sharir_scc(&self) -> KosarajuSharirSCC {
         KosarajuSharirSCC::new(self)
     }
-}
-
-pub enum SearchSource {
-    Single(usize),
-    Multi(Vec<usize>),
-}
 
-impl SearchSource {
-    fn iter(&self) -> ::std::vec::IntoIter<usize> {
-        match *self {
-            SearchSource::Single(ref i) => vec![*i].into_iter(),
-            SearchSource::Multi(ref vs) => vs.clone().into_iter(),
-        }
+    pub fn tarjan_scc(&self) -> TarjanSCC {
+        TarjanSCC::new(self)
     }
 
-    fn contains(&self, v: usize) -> bool {
-        match *self {
-            SearchSource::Single(ref i) => *i == v,
-            SearchSource::Multi(ref vs) => vs.contains(&v),
-        }
+    pub fn gabow_scc(&self) -> GabowSCC {
+        GabowSCC::new(self)
     }
-}
-
-pub struct SearchPaths<'a> {
-    graph: &'a Digraph,
-    marked: Vec<bool>,
-    edge_to: Vec<Option<usize>>,
-    source: SearchSource,
-}
 
-impl<'a> SearchPaths<'a> {
-    fn new(graph: &Digraph, source: SearchSource) -> SearchPaths {
-        let mut marked = iter::repeat(false).take(graph.v()).collect::<Vec<bool>>();
-        let edge_to = iter::repeat(None).take(graph.v()).collect();
-        for s in source.iter() {
-            marked[s] = true;
-        }
+    pub fn directed_cycle(&self) -> DirectedCycle {
+        DirectedCycle::new(self)
+    }
 
-        SearchPaths {
-            graph: graph,
-            marked: marked,
-            edge_to: edge_to,
-            source: source,
+    /// Topological order by reverse DFS postorder, or a directed cycle if the digraph is not a DAG.
+    pub fn topological_order(&self) -> Result<Topological, Vec<usize>> {
+        if let Some(cycle) = self.directed_cycle().cycle() {
+            return Err(cycle.to_vec());
         }
+        Ok(Topological::new(self.reverse_dfs_postorder().collect()))
     }
 
-    fn dfs_from(&mut self, v: usize) {
-        self.marked[v] = true;
-        for w in self.graph.adj(v) {
-            if !self.marked[w] {
-                self.dfs_from(w);
-                self.edge_to[w] = Some(v);
+    /// Lexicographically smallest topological order by Kahn's algorithm, or a directed cycle if the digraph
+    /// is not a DAG.
+    pub fn lexicographic_topological_order(&self) -> Result<Topological, Vec<usize>> {
+        let mut indegree = vec![0; self.v];
+        for v in 0..self.v {
+            for w in self.adj[v].iter() {
+                indegree[*w] += 1;
             }
         }
-    }
 
-    fn dfs(&mut self) {
-        for v in self.source.iter() {
-            self.dfs_from(v);
+        let mut pq = MinPQ::new();
+        for (v, &d) in indegree.iter().enumerate() {
+            if d == 0 {
+                pq.insert(v);
+            }
         }
-    }
 
-    fn bfs(&mut self) {
-        let mut q = Queue::new();
-        for s in self.source.iter() {
-            q.enqueue(s);
-        }
-        while !q.is_empty() {
-            let v = q.dequeue().unwrap();
-            for w in self.graph.adj(v) {
-                if !self.marked[w] {
-                    self.edge_to[w] = Some(v);
-                    q.enqueue(w);
-                    self.marked[w] = true;
+        let mut order = Vec::with_capacity(self.v);
+        while !pq.is_empty() {
+            let v = pq.del_min().unwrap();
+            order.push(v);
+            for w in self.adj[v].iter() {
+                indegree[*w] -= 1;
+                if indegree[*w] == 0 {
+                    pq.insert(*w);
                 }
             }
         }
+
+        if order.len() == self.v {
+            Ok(Topological::new(order))
+        } else {
+            Err(self.directed_cycle().cycle().unwrap().to_vec())
+        }
+    }
+
+    /// Reachability between all pairs of vertices, by a depth-first search from each of them.
+    pub fn transitive_closure(&self) -> TransitiveClosure {
+        TransitiveClosure::new(self)
+    }
+
+    /// Reachability between all pairs of vertices, stored per strongly-connected component.
+    pub fn condensed_closure(&self) -> CondensedClosure {
+        CondensedClosure::new(self)
+    }
+
+    /// The DAG with the fewest edges and the same reachability, or a directed cycle if the digraph is not a DAG.
+    pub fn transitive_reduction(&self) -> Result<Digraph, Vec<usize>> {
+        reachability::transitive_reduction(self)
+    }
+
+    /// Edges of a directed cycle using every edge exactly once, by Hierholzer's algorithm.
+    pub fn eulerian_cycle(&self) -> Result<Vec<(usize, usize)>, EulerianError> {
+        euler::directed_eulerian_cycle(self)
+    }
+
+    /// Edges of a directed path using every edge exactly once, by Hierholzer's algorithm.
+    pub fn eulerian_path(&self) -> Result<Vec<(usize, usize)>, EulerianError> {
+        euler::directed_eulerian_path(self)
+    }
+
+    /// A directed path visiting every vertex exactly once. Only for small digraphs, see
+    /// `hamiltonian::hamiltonian_path`.
+    pub fn hamiltonian_path(&self) -> Option<Vec<usize>> {
+        hamiltonian::hamiltonian_path(self)
     }
+}
 
-    pub fn has_path_to(&self, v: usize) -> bool {
-        self.marked[v]
+impl GraphView for Digraph {
+    type Neighbors<'a> = iter::Cloned<bag::Iter<'a, usize>>;
+
+    fn vertex_count(&self) -> usize {
+        self.v
     }
 
-    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
-        if self.has_path_to(v) {
-            let mut path = Stack::new();
-            let mut x = v;
-            while !self.source.contains(x) {
-                path.push(x);
-                x = self.edge_to[x].unwrap();
+    fn edge_count(&self) -> usize {
+        self.e
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.adj[v].iter().cloned()
+    }
+}
+
+/// Find a directed cycle in a digraph.
+pub struct DirectedCycle {
+    cycle: Option<Vec<usize>>,
+}
+
+impl DirectedCycle {
+    fn new(graph: &Digraph) -> DirectedCycle {
+        let n = graph.v();
+        let mut finder = DirectedCycleFinder {
+            graph: graph,
+            marked: iter::repeat(false).take(n).collect(),
+            on_stack: iter::repeat(false).take(n).collect(),
+            edge_to: iter::repeat(None).take(n).collect(),
+            cycle: None,
+        };
+        for v in 0..n {
+            if !finder.marked[v] && finder.cycle.is_none() {
+                finder.dfs(v);
             }
-            path.push(x);
-            Some(path.into_iter().collect())
-        } else {
-            None
         }
+        DirectedCycle { cycle: finder.cycle }
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// Vertices of the cycle, the first and the last one being the same.
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_ref().map(|c| &c[..])
     }
 }
 
-pub struct DepthFirstOrder<'a> {
+struct DirectedCycleFinder<'a> {
     graph: &'a Digraph,
     marked: Vec<bool>,
-    reverse_post: Stack<usize>,
+    on_stack: Vec<bool>,
+    edge_to: Vec<Option<usize>>,
+    cycle: Option<Vec<usize>>,
 }
 
-impl<'a> DepthFirstOrder<'a> {
-    fn new(graph: &Digraph) -> DepthFirstOrder {
-        let marked = iter::repeat(false).take(graph.v()).collect();
-        DepthFirstOrder {
-            graph: graph,
-            marked: marked,
-            reverse_post: Stack::new(),
+impl<'a> DirectedCycleFinder<'a> {
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.marked[s] = true;
+        self.on_stack[s] = true;
+        stack.push((s, graph.adj[s].iter()));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(&w) if !self.marked[w] => {
+                    self.marked[w] = true;
+                    self.on_stack[w] = true;
+                    self.edge_to[w] = Some(v);
+                    stack.push((w, graph.adj[w].iter()));
+                }
+                Some(&w) if self.on_stack[w] => {
+                    let mut cycle = Stack::new();
+                    let mut x = v;
+                    while x != w {
+                        cycle.push(x);
+                        x = self.edge_to[x].unwrap();
+                    }
+                    cycle.push(w);
+                    cycle.push(v);
+                    self.cycle = Some(cycle.into_iter().collect());
+                    return;
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                    self.on_stack[v] = false;
+                }
+            }
         }
     }
+}
 
-    fn init(&mut self) {
-        for v in 0..self.graph.v() {
-            if !self.marked[v] {
-                self.dfs(v)
-            }
+/// A topological order of a DAG.
+pub struct Topological {
+    order: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl Topological {
+    fn new(order: Vec<usize>) -> Topological {
+        let mut rank = iter::repeat(0).take(order.len()).collect::<Vec<usize>>();
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i;
+        }
+        Topological {
+            order: order,
+            rank: rank,
         }
     }
 
-    fn dfs(&mut self, v: usize) {
-        self.marked[v] = true;
-        for w in self.graph.adj(v) {
-            if !self.marked[w] {
-                self.dfs(w);
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Position of `v` in the topological order.
+    pub fn rank(&self, v: usize) -> usize {
+        self.rank[v]
+    }
+}
+
+/// Strongly-connected components of a digraph, numbered from `0` to `count() - 1`.
+pub trait StronglyConnectedComponents {
+    type Graph: GraphView;
+
+    fn graph(&self) -> &Self::Graph;
+
+    fn count(&self) -> usize;
+
+    /// Component identifier of `v`.
+    fn id(&self, v: usize) -> usize;
+
+    /// Are `v` and `w` mutually reachable?
+    fn connected(&self, v: usize, w: usize) -> bool {
+        self.id(v) == self.id(w)
+    }
+
+    /// Vertices of each component, indexed by component identifier.
+    fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = iter::repeat(Vec::new()).take(self.count()).collect::<Vec<Vec<usize>>>();
+        for v in 0..self.graph().vertex_count() {
+            components[self.id(v)].push(v);
+        }
+        components
+    }
+
+    /// The kernel DAG: one vertex per component, and an edge between two components whenever an edge of
+    /// the digraph joins them.
+    fn condensation(&self) -> Digraph {
+        let mut dag = Digraph::new(self.count());
+        let mut edges = Vec::new();
+        for v in 0..self.graph().vertex_count() {
+            for w in self.graph().neighbors(v) {
+                if self.id(v) != self.id(w) {
+                    edges.push((self.id(v), self.id(w)));
+                }
             }
         }
-        self.reverse_post.push(v);
+        edges.sort();
+        edges.dedup();
+        for (v, w) in edges {
+            dag.add_edge(v, w);
+        }
+        dag
     }
 }
 
@@ -297,14 +493,227 @@ impl<'a> KosarajuSharirSCC<'a> {
         self.id[v] == self.id[w]
     }
 
-    fn dfs(&mut self, v: usize) {
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.marked[s] = true;
+        self.id[s] = Some(self.count);
+        stack.push(graph.adj[s].iter());
+        while let Some(adj) = stack.peek_mut() {
+            match adj.next() {
+                Some(&w) if !self.marked[w] => {
+                    self.marked[w] = true;
+                    self.id[w] = Some(self.count);
+                    stack.push(graph.adj[w].iter());
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a> StronglyConnectedComponents for KosarajuSharirSCC<'a> {
+    type Graph = Digraph;
+
+    fn graph(&self) -> &Digraph {
+        self.graph
+    }
+
+    fn count(&self) -> usize {
+        KosarajuSharirSCC::count(self)
+    }
+
+    fn id(&self, v: usize) -> usize {
+        KosarajuSharirSCC::id(self, v)
+    }
+}
+
+/// Compute the strongly-connected components of a digraph using Tarjan's
+/// algorithm, in a single depth-first search.
+pub struct TarjanSCC<'a, G: 'a = Digraph> {
+    graph: &'a G,
+    marked: Vec<bool>,
+    id: Vec<usize>,
+    low: Vec<usize>,
+    pre: usize,
+    count: usize,
+    stack: Stack<usize>,
+}
+
+impl<'a, G: GraphView> TarjanSCC<'a, G> {
+    pub fn new(graph: &'a G) -> TarjanSCC<'a, G> {
+        let n = graph.vertex_count();
+        let mut cc = TarjanSCC {
+            graph: graph,
+            marked: iter::repeat(false).take(n).collect(),
+            id: iter::repeat(0).take(n).collect(),
+            low: iter::repeat(0).take(n).collect(),
+            pre: 0,
+            count: 0,
+            stack: Stack::new(),
+        };
+        for v in 0..n {
+            if !cc.marked[v] {
+                cc.dfs(v);
+            }
+        }
+        cc
+    }
+
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        // each frame keeps the vertex, its remaining neighbours and its preorder number
+        let mut stack = Stack::new();
+        self.visit(s);
+        stack.push((s, graph.neighbors(s), self.low[s]));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(w) if !self.marked[w] => {
+                    self.visit(w);
+                    stack.push((w, graph.neighbors(w), self.low[w]));
+                }
+                Some(w) => {
+                    self.low[v] = cmp::min(self.low[v], self.low[w]);
+                }
+                None => {
+                    let min = frame.2;
+                    stack.pop();
+                    if self.low[v] >= min {
+                        // v is the root of a component, pop it off the stack
+                        while let Some(w) = self.stack.pop() {
+                            self.id[w] = self.count;
+                            self.low[w] = graph.vertex_count();
+                            if w == v {
+                                break;
+                            }
+                        }
+                        self.count += 1;
+                    }
+                    if let Some(&(u, _, _)) = stack.peek() {
+                        self.low[u] = cmp::min(self.low[u], self.low[v]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit(&mut self, v: usize) {
         self.marked[v] = true;
-        self.id[v] = Some(self.count);
-        for w in self.graph.adj(v) {
-            if !self.marked[w] {
-                self.dfs(w)
+        self.low[v] = self.pre;
+        self.pre += 1;
+        self.stack.push(v);
+    }
+}
+
+impl<'a, G: GraphView> StronglyConnectedComponents for TarjanSCC<'a, G> {
+    type Graph = G;
+
+    fn graph(&self) -> &G {
+        self.graph
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+}
+
+/// Compute the strongly-connected components of a digraph using Gabow's
+/// path-based algorithm, in a single depth-first search.
+pub struct GabowSCC<'a, G: 'a = Digraph> {
+    graph: &'a G,
+    pre: Vec<Option<usize>>,
+    id: Vec<Option<usize>>,
+    counter: usize,
+    count: usize,
+    stack1: Stack<usize>,
+    stack2: Stack<usize>,
+}
+
+impl<'a, G: GraphView> GabowSCC<'a, G> {
+    pub fn new(graph: &'a G) -> GabowSCC<'a, G> {
+        let n = graph.vertex_count();
+        let mut cc = GabowSCC {
+            graph: graph,
+            pre: iter::repeat(None).take(n).collect(),
+            id: iter::repeat(None).take(n).collect(),
+            counter: 0,
+            count: 0,
+            stack1: Stack::new(),
+            stack2: Stack::new(),
+        };
+        for v in 0..n {
+            if cc.pre[v].is_none() {
+                cc.dfs(v);
             }
         }
+        cc
+    }
+
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.visit(s);
+        stack.push((s, graph.neighbors(s)));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(w) if self.pre[w].is_none() => {
+                    self.visit(w);
+                    stack.push((w, graph.neighbors(w)));
+                }
+                Some(w) if self.id[w].is_none() => {
+                    // contract the cycle closed by v -> w
+                    while self.pre[*self.stack2.peek().unwrap()] > self.pre[w] {
+                        self.stack2.pop();
+                    }
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                    if self.stack2.peek() == Some(&v) {
+                        self.stack2.pop();
+                        while let Some(w) = self.stack1.pop() {
+                            self.id[w] = Some(self.count);
+                            if w == v {
+                                break;
+                            }
+                        }
+                        self.count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.pre[v] = Some(self.counter);
+        self.counter += 1;
+        self.stack1.push(v);
+        self.stack2.push(v);
+    }
+}
+
+impl<'a, G: GraphView> StronglyConnectedComponents for GabowSCC<'a, G> {
+    type Graph = G;
+
+    fn graph(&self) -> &G {
+        self.graph
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn id(&self, v: usize) -> usize {
+        self.id[v].unwrap()
     }
 }
 
@@ -415,3 +824,171 @@ fn test_digraph_depth_first_search_order() {
     let dfo: Vec<usize> = g.reverse_dfs_postorder().collect();
     assert_eq!(vec![3, 6, 0, 5, 2, 1, 4], dfo);
 }
+
+#[cfg(test)]
+fn is_directed_cycle(g: &Digraph, cycle: &[usize]) -> bool {
+    cycle.len() >= 2 && cycle.first() == cycle.last() && cycle.windows(2).all(|e| g.adj(e[0]).contains(&e[1]))
+}
+
+#[test]
+fn test_digraph_topological_order() {
+    let mut g = Digraph::new(7);
+    g.add_edge(0, 2);
+    g.add_edge(0, 5);
+    g.add_edge(0, 1);
+    g.add_edge(6, 0);
+    g.add_edge(5, 2);
+    g.add_edge(3, 2);
+    g.add_edge(3, 5);
+    g.add_edge(1, 4);
+    g.add_edge(3, 4);
+    g.add_edge(3, 6);
+    g.add_edge(6, 4);
+
+    assert!(!g.directed_cycle().has_cycle());
+
+    let topo = g.topological_order().unwrap();
+    assert_eq!(&[3, 6, 0, 5, 2, 1, 4], topo.order());
+    assert_eq!(0, topo.rank(3));
+    assert_eq!(6, topo.rank(4));
+
+    let topo = g.lexicographic_topological_order().unwrap();
+    assert_eq!(&[3, 6, 0, 1, 4, 5, 2], topo.order());
+
+    g.add_edge(2, 6);
+    let cycle = g.directed_cycle();
+    assert!(cycle.has_cycle());
+    assert!(is_directed_cycle(&g, cycle.cycle().unwrap()));
+    assert!(is_directed_cycle(&g, &g.topological_order().err().unwrap()));
+    assert!(is_directed_cycle(&g, &g.lexicographic_topological_order().err().unwrap()));
+}
+
+#[test]
+fn test_digraph_self_loop_cycle() {
+    let mut g = Digraph::new(3);
+    g.add_edge(0, 1);
+    g.add_edge(1, 1);
+
+    assert_eq!(Some(&[1, 1][..]), g.directed_cycle().cycle());
+    assert!(g.topological_order().is_err());
+}
+
+#[test]
+fn test_digraph_strongly_connected_components() {
+    let mut g = Digraph::new(13);
+    for &(v, w) in &[
+        (4, 2),
+        (2, 3),
+        (3, 2),
+        (6, 0),
+        (0, 1),
+        (2, 0),
+        (11, 12),
+        (12, 9),
+        (9, 10),
+        (9, 11),
+        (7, 9),
+        (10, 12),
+        (11, 4),
+        (4, 3),
+        (3, 5),
+        (6, 8),
+        (8, 6),
+        (5, 4),
+        (0, 5),
+        (6, 4),
+        (6, 9),
+        (7, 6),
+    ] {
+        g.add_edge(v, w);
+    }
+
+    fn check<S: StronglyConnectedComponents>(scc: &S) {
+        assert_eq!(5, scc.count());
+        let mut components = scc.components();
+        components.sort();
+        assert_eq!(vec![vec![0, 2, 3, 4, 5], vec![1], vec![6, 8], vec![7], vec![9, 10, 11, 12]], components);
+        assert!(scc.connected(0, 4));
+        assert!(!scc.connected(1, 0));
+
+        let dag = scc.condensation();
+        assert_eq!(5, dag.v());
+        assert_eq!(6, dag.e());
+        assert!(dag.topological_order().is_ok());
+        assert!(dag.adj(scc.id(7)).contains(&scc.id(6)));
+        assert!(dag.adj(scc.id(11)).contains(&scc.id(0)));
+    }
+
+    check(&g.kosaraju_sharir_scc());
+    check(&g.tarjan_scc());
+    check(&g.gabow_scc());
+
+    // Tarjan's and Gabow's algorithms number components in reverse topological order
+    let topo = g.tarjan_scc().condensation().topological_order().unwrap();
+    assert_eq!(&[4, 3, 2, 1, 0], topo.order());
+    let topo = g.gabow_scc().condensation().topological_order().unwrap();
+    assert_eq!(&[4, 3, 2, 1, 0], topo.order());
+}
+
+#[test]
+fn test_digraph_mutation() {
+    let mut g = Digraph::new(4);
+    g.add_edge(0, 1);
+    g.add_edge(0, 1);
+    g.add_edge(1, 2);
+    g.add_edge(2, 0);
+    g.add_edge(3, 3);
+    g.add_edge(3, 0);
+    assert!(g.has_edge(0, 1) && !g.has_edge(1, 0));
+    assert!(g.remove_edge(0, 1));
+    assert!(g.has_edge(0, 1));
+    assert!(!g.remove_edge(1, 0));
+    assert_eq!(5, g.e());
+
+    let v = g.add_vertex();
+    g.add_edge(v, 2);
+    assert_eq!((5, 6), (g.v(), g.e()));
+
+    // 4 takes the place of 0, and then 3 that of 1
+    g.remove_vertex(0);
+    assert_eq!((4, 3), (g.v(), g.e()));
+    assert_eq!(vec![(0, 2), (1, 2), (3, 3)], {
+        let mut edges = g.all_edges();
+        edges.sort();
+        edges
+    });
+    g.remove_vertex(1);
+    assert_eq!(vec![(0, 2), (1, 1)], {
+        let mut edges = g.all_edges();
+        edges.sort();
+        edges
+    });
+    g.remove_vertex(2);
+    g.remove_vertex(1);
+    g.remove_vertex(0);
+    assert_eq!((0, 0), (g.v(), g.e()));
+}
+
+#[test]
+fn test_digraph_long_path() {
+    let n = 1_000_000;
+    let mut g = Digraph::new(n);
+    for v in 1..n {
+        g.add_edge(v - 1, v);
+    }
+
+    assert_eq!(Some(n), g.dfs(0).path_to(n - 1).map(|path| path.len()));
+    assert_eq!(Some(n), g.bfs(0).path_to(n - 1).map(|path| path.len()));
+    assert_eq!(Some(0), g.reverse_dfs_postorder().next());
+    assert!(!g.directed_cycle().has_cycle());
+    assert_eq!(n - 1, g.topological_order().unwrap().rank(n - 1));
+    assert_eq!(n, g.kosaraju_sharir_scc().count());
+    assert_eq!(n, g.tarjan_scc().count());
+    assert_eq!(n, g.gabow_scc().count());
+
+    g.add_edge(n - 1, 0);
+    assert_eq!(Some(n + 1), g.directed_cycle().cycle().map(|c| c.len()));
+    assert_eq!(1, g.kosaraju_sharir_scc().count());
+    assert_eq!(1, g.tarjan_scc().count());
+    assert_eq!(1, g.gabow_scc().count());
+}
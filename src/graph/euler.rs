@@ -0,0 +1,235 @@
+use super::super::stack::Stack;
+use super::{Digraph, Graph};
+use std::error;
+use std::fmt;
+use std::iter;
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// Why a graph has no Eulerian cycle or path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EulerianError {
+    /// The vertices of odd degree, more than a cycle (none) or a path (two) allows.
+    OddDegree(Vec<usize>),
+    /// The vertices whose indegree and outdegree differ, more than a cycle (none) or a path (one with an extra
+    /// outgoing edge, one with an extra incoming edge) allows.
+    Unbalanced(Vec<usize>),
+    /// Some edges, such as one at `unreachable`, cannot be reached from the edges at `from`.
+    Disconnected { from: usize, unreachable: usize },
+}
+
+impl fmt::Display for EulerianError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EulerianError::OddDegree(ref vs) => write!(f, "vertices {:?} have odd degree", vs),
+            EulerianError::Unbalanced(ref vs) => write!(f, "vertices {:?} have unequal indegree and outdegree", vs),
+            EulerianError::Disconnected { from, unreachable } => {
+                write!(f, "edges at vertex {} cannot be reached from vertex {}", unreachable, from)
+            }
+        }
+    }
+}
+
+impl error::Error for EulerianError {}
+
+/// Hierholzer's algorithm: walk unused edges from `s` until stuck, then back up and splice in the detours found
+/// on the way back. Returns the edges in walking order, unless some of them are not connected to `s`.
+fn hierholzer(
+    v: usize,
+    edges: &[(usize, usize)],
+    directed: bool,
+    s: usize,
+) -> Result<Vec<(usize, usize)>, EulerianError> {
+    let mut incident = iter::repeat(Vec::new()).take(v).collect::<Vec<Vec<usize>>>();
+    for (i, &(a, b)) in edges.iter().enumerate() {
+        incident[a].push(i);
+        if !directed && a != b {
+            incident[b].push(i);
+        }
+    }
+    let mut used = iter::repeat(false).take(edges.len()).collect::<Vec<bool>>();
+    let mut next = iter::repeat(0).take(v).collect::<Vec<usize>>();
+
+    let mut walk = Vec::with_capacity(edges.len());
+    let mut stack = Stack::new();
+    stack.push((s, None));
+    while let Some(&(x, edge)) = stack.peek() {
+        while next[x] < incident[x].len() && used[incident[x][next[x]]] {
+            next[x] += 1;
+        }
+        if next[x] < incident[x].len() {
+            let i = incident[x][next[x]];
+            used[i] = true;
+            let (a, b) = edges[i];
+            let y = if a == x { b } else { a };
+            stack.push((y, Some((x, y))));
+        } else {
+            stack.pop();
+            if let Some(e) = edge {
+                walk.push(e);
+            }
+        }
+    }
+    if let Some(i) = used.iter().position(|&u| !u) {
+        return Err(EulerianError::Disconnected {
+            from: s,
+            unreachable: edges[i].0,
+        });
+    }
+    walk.reverse();
+    Ok(walk)
+}
+
+fn graph_euler(graph: &Graph, path: bool) -> Result<Vec<(usize, usize)>, EulerianError> {
+    let odd = (0..graph.vertices())
+        .filter(|&v| graph.degree(v) % 2 == 1)
+        .collect::<Vec<usize>>();
+    if odd.len() > if path { 2 } else { 0 } {
+        return Err(EulerianError::OddDegree(odd));
+    }
+    let edges = graph.all_edges();
+    let s = match odd.first() {
+        Some(&s) => s,
+        None => match edges.first() {
+            Some(&(s, _)) => s,
+            None => return Ok(Vec::new()),
+        },
+    };
+    hierholzer(graph.vertices(), &edges, false, s)
+}
+
+fn digraph_euler(graph: &Digraph, path: bool) -> Result<Vec<(usize, usize)>, EulerianError> {
+    let edges = graph.all_edges();
+    let mut balance = iter::repeat(0isize).take(graph.v()).collect::<Vec<isize>>();
+    for &(a, b) in &edges {
+        balance[a] += 1;
+        balance[b] -= 1;
+    }
+    let unbalanced = (0..graph.v()).filter(|&v| balance[v] != 0).collect::<Vec<usize>>();
+    let starts = unbalanced.iter().filter(|&&v| balance[v] == 1).count();
+    let ends = unbalanced.iter().filter(|&&v| balance[v] == -1).count();
+    let path_ends = path && unbalanced.len() == 2 && starts == 1 && ends == 1;
+    if !unbalanced.is_empty() && !path_ends {
+        return Err(EulerianError::Unbalanced(unbalanced));
+    }
+    let s = match unbalanced.iter().find(|&&v| balance[v] == 1) {
+        Some(&s) => s,
+        None => match edges.first() {
+            Some(&(s, _)) => s,
+            None => return Ok(Vec::new()),
+        },
+    };
+    hierholzer(graph.v(), &edges, true, s)
+}
+
+/// Edges of a cycle using every edge of `graph` exactly once, in walking order.
+pub fn eulerian_cycle(graph: &Graph) -> Result<Vec<(usize, usize)>, EulerianError> {
+    graph_euler(graph, false)
+}
+
+/// Edges of a path using every edge of `graph` exactly once, in walking order. A cycle is a path too.
+pub fn eulerian_path(graph: &Graph) -> Result<Vec<(usize, usize)>, EulerianError> {
+    graph_euler(graph, true)
+}
+
+/// Edges of a directed cycle using every edge of `graph` exactly once, in walking order.
+pub fn directed_eulerian_cycle(graph: &Digraph) -> Result<Vec<(usize, usize)>, EulerianError> {
+    digraph_euler(graph, false)
+}
+
+/// Edges of a directed path using every edge of `graph` exactly once, in walking order.
+pub fn directed_eulerian_path(graph: &Digraph) -> Result<Vec<(usize, usize)>, EulerianError> {
+    digraph_euler(graph, true)
+}
+
+#[cfg(test)]
+fn is_walk_of(edges: &[(usize, usize)], walk: &[(usize, usize)], directed: bool) -> bool {
+    let normalize = |&(a, b): &(usize, usize)| if directed || a <= b { (a, b) } else { (b, a) };
+    let mut expected = edges.iter().map(&normalize).collect::<Vec<_>>();
+    let mut got = walk.iter().map(&normalize).collect::<Vec<_>>();
+    expected.sort();
+    got.sort();
+    expected == got && walk.windows(2).all(|w| w[0].1 == w[1].0)
+}
+
+#[test]
+fn test_graph_eulerian() {
+    // two triangles sharing vertex 0, and a self-loop at 3
+    let g: Graph = "5 7\n0 1\n1 2\n2 0\n0 3\n3 4\n4 0\n3 3".parse().unwrap();
+    let cycle = g.eulerian_cycle().unwrap();
+    assert_eq!(7, cycle.len());
+    assert!(is_walk_of(&g.all_edges(), &cycle, false));
+    assert_eq!(cycle.first().unwrap().0, cycle.last().unwrap().1);
+
+    let mut g = g;
+    g.add_edge(1, 4);
+    assert_eq!(Err(EulerianError::OddDegree(vec![1, 4])), g.eulerian_cycle());
+    let path = g.eulerian_path().unwrap();
+    assert!(is_walk_of(&g.all_edges(), &path, false));
+    assert_eq!((1, 4), (path[0].0, path[7].1));
+
+    g.add_edge(2, 3);
+    assert_eq!(Err(EulerianError::OddDegree(vec![1, 2, 3, 4])), g.eulerian_path());
+
+    let g: Graph = "6 6\n0 1\n1 2\n2 0\n3 4\n4 5\n5 3".parse().unwrap();
+    match g.eulerian_cycle() {
+        Err(EulerianError::Disconnected { from, unreachable }) => assert!(from < 3 && unreachable >= 3),
+        other => panic!("unexpected {:?}", other),
+    }
+    let err = EulerianError::Disconnected {
+        from: 0,
+        unreachable: 3,
+    };
+    assert_eq!("edges at vertex 3 cannot be reached from vertex 0", err.to_string());
+
+    assert_eq!(Ok(vec![]), Graph::new(3).eulerian_cycle());
+}
+
+#[test]
+fn test_digraph_eulerian() {
+    let g: Digraph = "4 6\n0 1\n1 2\n2 0\n0 2\n2 3\n3 0".parse().unwrap();
+    let cycle = g.eulerian_cycle().unwrap();
+    assert!(is_walk_of(&g.all_edges(), &cycle, true));
+    assert_eq!(cycle.first().unwrap().0, cycle.last().unwrap().1);
+
+    let mut g = g;
+    g.add_edge(1, 3);
+    assert_eq!(Err(EulerianError::Unbalanced(vec![1, 3])), g.eulerian_cycle());
+    let path = g.eulerian_path().unwrap();
+    assert!(is_walk_of(&g.all_edges(), &path, true));
+    assert_eq!((1, 3), (path[0].0, path[6].1));
+
+    let g: Digraph = "3 2\n0 1\n2 1".parse().unwrap();
+    assert_eq!(Err(EulerianError::Unbalanced(vec![0, 1, 2])), g.eulerian_path());
+
+    let g: Digraph = "4 4\n0 1\n1 0\n2 3\n3 2".parse().unwrap();
+    match g.eulerian_cycle() {
+        Err(EulerianError::Disconnected { from, unreachable }) => assert!((from < 2) != (unreachable < 2)),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn quicktest_eulerian_cycles() {
+    // a union of random closed walks has an Eulerian cycle
+    fn prop(walks: Vec<Vec<u8>>) -> bool {
+        let mut g = Digraph::new(256);
+        let mut u = Graph::new(256);
+        for walk in walks.iter().filter(|w| !w.is_empty()) {
+            // every walk passes through vertex 0 to keep the edges connected
+            let closed = iter::once(0)
+                .chain(walk.iter().map(|&v| v as usize))
+                .chain(iter::once(0))
+                .collect::<Vec<_>>();
+            for e in closed.windows(2) {
+                g.add_edge(e[0], e[1]);
+                u.add_edge(e[0], e[1]);
+            }
+        }
+        let directed = g.eulerian_cycle().map(|c| is_walk_of(&g.all_edges(), &c, true));
+        let undirected = u.eulerian_cycle().map(|c| is_walk_of(&u.all_edges(), &c, false));
+        directed == Ok(true) && undirected == Ok(true)
+    }
+    quickcheck(prop as fn(Vec<Vec<u8>>) -> bool);
+}
@@ -0,0 +1,445 @@
+use super::super::bag::Bag;
+use super::super::queue::Queue;
+use super::super::stack::Stack;
+use std::f64;
+use std::iter;
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// A capacitated edge from `v` to `w` with a flow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowEdge {
+    v: usize,
+    w: usize,
+    capacity: f64,
+    flow: f64,
+}
+
+impl FlowEdge {
+    pub fn new(v: usize, w: usize, capacity: f64) -> FlowEdge {
+        assert!(capacity >= 0.0, "edge capacity must be nonnegative");
+        FlowEdge {
+            v: v,
+            w: w,
+            capacity: capacity,
+            flow: 0.0,
+        }
+    }
+
+    pub fn from(&self) -> usize {
+        self.v
+    }
+
+    pub fn to(&self) -> usize {
+        self.w
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    pub fn flow(&self) -> f64 {
+        self.flow
+    }
+
+    /// The endpoint of the edge that is different from `vertex`.
+    pub fn other(&self, vertex: usize) -> usize {
+        if vertex == self.v {
+            self.w
+        } else if vertex == self.w {
+            self.v
+        } else {
+            panic!("illegal endpoint")
+        }
+    }
+
+    /// Residual capacity of the edge in the direction to `vertex`: the unused capacity forwards, the flow
+    /// backwards.
+    pub fn residual_capacity_to(&self, vertex: usize) -> f64 {
+        if vertex == self.v {
+            self.flow
+        } else if vertex == self.w {
+            self.capacity - self.flow
+        } else {
+            panic!("illegal endpoint")
+        }
+    }
+
+    fn add_residual_flow_to(&mut self, vertex: usize, delta: f64) {
+        if vertex == self.v {
+            self.flow -= delta;
+        } else if vertex == self.w {
+            self.flow += delta;
+        } else {
+            panic!("illegal endpoint")
+        }
+    }
+}
+
+/// A flow network: a digraph of capacitated edges.
+#[derive(Clone, Debug)]
+pub struct FlowNetwork {
+    v: usize,
+    edges: Vec<FlowEdge>,
+    // indices into `edges` of the edges incident to each vertex, in either direction
+    adj: Vec<Bag<usize>>,
+}
+
+impl FlowNetwork {
+    pub fn new(v: usize) -> FlowNetwork {
+        FlowNetwork {
+            v: v,
+            edges: Vec::new(),
+            adj: iter::repeat(Bag::<usize>::new()).take(v).collect(),
+        }
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(v < self.v, "vertex is not between 0 and {}", self.v - 1)
+    }
+
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    pub fn e(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_edge(&mut self, e: FlowEdge) {
+        self.validate_vertex(e.from());
+        self.validate_vertex(e.to());
+
+        let i = self.edges.len();
+        self.edges.push(e);
+        self.adj[e.from()].add(i);
+        self.adj[e.to()].add(i);
+    }
+
+    /// Edges incident to `v`, both leaving and entering it.
+    pub fn adj(&self, v: usize) -> Vec<FlowEdge> {
+        self.adj[v].iter().map(|&i| self.edges[i]).collect()
+    }
+
+    /// All edges, in the order they were added.
+    pub fn all_edges(&self) -> &[FlowEdge] {
+        &self.edges
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        dot.push_str("digraph G {\n");
+        for i in 0..self.v {
+            dot.push_str(&format!("  {};\n", i));
+        }
+
+        for e in &self.edges {
+            dot.push_str(&format!("  {} -> {} [label=\"{}/{}\"];\n", e.from(), e.to(), e.flow(), e.capacity()));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Maximum flow from `s` to `t` by the Ford-Fulkerson method, augmenting along shortest paths
+    /// (Edmonds-Karp).
+    pub fn edmonds_karp(&self, s: usize, t: usize) -> MaxFlow {
+        let mut mf = MaxFlow::new(self, s, t);
+        loop {
+            let edge_to = mf.residual_bfs(self);
+            if edge_to[t].is_none() {
+                break;
+            }
+
+            // bottleneck capacity of the augmenting path
+            let mut bottle = f64::INFINITY;
+            let mut v = t;
+            while v != s {
+                let e = &mf.edges[edge_to[v].unwrap()];
+                bottle = bottle.min(e.residual_capacity_to(v));
+                v = e.other(v);
+            }
+
+            let mut v = t;
+            while v != s {
+                let e = &mut mf.edges[edge_to[v].unwrap()];
+                e.add_residual_flow_to(v, bottle);
+                v = e.other(v);
+            }
+            mf.value += bottle;
+        }
+        mf.finish(self);
+        mf
+    }
+
+    /// Maximum flow from `s` to `t` by Dinic's algorithm: blocking flows on the level graph of the residual
+    /// network.
+    pub fn dinic(&self, s: usize, t: usize) -> MaxFlow {
+        let mut mf = MaxFlow::new(self, s, t);
+        let adj = self
+            .adj
+            .iter()
+            .map(|a| a.iter().cloned().collect())
+            .collect::<Vec<Vec<usize>>>();
+        loop {
+            let level = mf.levels(self);
+            if level[t].is_none() {
+                break;
+            }
+
+            // position of the next edge to try at each vertex, dead ends are never tried twice
+            let mut next = vec![0; self.v];
+            let mut path: Stack<usize> = Stack::new();
+            let mut v = s;
+            loop {
+                if v == t {
+                    let mut bottle = f64::INFINITY;
+                    let mut x = t;
+                    for &i in path.iter() {
+                        bottle = bottle.min(mf.edges[i].residual_capacity_to(x));
+                        x = mf.edges[i].other(x);
+                    }
+                    let mut x = t;
+                    for &i in path.iter() {
+                        let e = &mut mf.edges[i];
+                        e.add_residual_flow_to(x, bottle);
+                        x = e.other(x);
+                    }
+                    mf.value += bottle;
+                    // restart from s, saturated edges are skipped on the way
+                    path = Stack::new();
+                    v = s;
+                    continue;
+                }
+
+                let mut advanced = false;
+                while next[v] < adj[v].len() {
+                    let e = &mf.edges[adj[v][next[v]]];
+                    let w = e.other(v);
+                    if e.residual_capacity_to(w) > 0.0 && level[w] == level[v].map(|l| l + 1) {
+                        path.push(adj[v][next[v]]);
+                        v = w;
+                        advanced = true;
+                        break;
+                    }
+                    next[v] += 1;
+                }
+                if advanced {
+                    continue;
+                }
+
+                // retreat from the dead end
+                match path.pop() {
+                    Some(i) => {
+                        v = mf.edges[i].other(v);
+                        next[v] += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+        mf.finish(self);
+        mf
+    }
+}
+
+/// A maximum flow, together with a minimum cut.
+pub struct MaxFlow {
+    s: usize,
+    t: usize,
+    value: f64,
+    edges: Vec<FlowEdge>,
+    marked: Vec<bool>,
+}
+
+impl MaxFlow {
+    fn new(network: &FlowNetwork, s: usize, t: usize) -> MaxFlow {
+        network.validate_vertex(s);
+        network.validate_vertex(t);
+        assert!(s != t, "source equals sink");
+
+        let mut edges = network.all_edges().to_vec();
+        for e in edges.iter_mut() {
+            e.flow = 0.0;
+        }
+        MaxFlow {
+            s: s,
+            t: t,
+            value: 0.0,
+            edges: edges,
+            marked: Vec::new(),
+        }
+    }
+
+    /// Breadth-first search in the residual network, returning the last edge on a shortest path to each
+    /// vertex reached.
+    fn residual_bfs(&self, network: &FlowNetwork) -> Vec<Option<usize>> {
+        let mut edge_to = vec![None; network.v()];
+        let mut marked = vec![false; network.v()];
+        let mut q = Queue::new();
+        marked[self.s] = true;
+        q.enqueue(self.s);
+        while let Some(v) = q.dequeue() {
+            for &i in network.adj[v].iter() {
+                let w = self.edges[i].other(v);
+                if !marked[w] && self.edges[i].residual_capacity_to(w) > 0.0 {
+                    edge_to[w] = Some(i);
+                    marked[w] = true;
+                    q.enqueue(w);
+                }
+            }
+        }
+        edge_to
+    }
+
+    /// Distance from the source of each vertex in the residual network.
+    fn levels(&self, network: &FlowNetwork) -> Vec<Option<usize>> {
+        let mut level = vec![None; network.v()];
+        let mut q = Queue::new();
+        level[self.s] = Some(0);
+        q.enqueue(self.s);
+        while let Some(v) = q.dequeue() {
+            for &i in network.adj[v].iter() {
+                let w = self.edges[i].other(v);
+                if level[w].is_none() && self.edges[i].residual_capacity_to(w) > 0.0 {
+                    level[w] = level[v].map(|l| l + 1);
+                    q.enqueue(w);
+                }
+            }
+        }
+        level
+    }
+
+    fn finish(&mut self, network: &FlowNetwork) {
+        self.marked = self.levels(network).iter().map(|l| l.is_some()).collect();
+    }
+
+    /// Value of the maximum flow.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Edges of the network, in the order they were added, carrying the flow.
+    pub fn edges(&self) -> &[FlowEdge] {
+        &self.edges
+    }
+
+    /// Is `v` on the source side of the minimum cut?
+    pub fn in_cut(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    /// Vertices on the source side of the minimum cut.
+    pub fn min_cut(&self) -> Vec<usize> {
+        (0..self.marked.len()).filter(|&v| self.marked[v]).collect()
+    }
+
+    pub fn source(&self) -> usize {
+        self.s
+    }
+
+    pub fn sink(&self) -> usize {
+        self.t
+    }
+}
+
+#[cfg(test)]
+fn check_max_flow(network: &FlowNetwork, mf: &MaxFlow) -> bool {
+    const EPSILON: f64 = 1e-9;
+
+    let mut excess = vec![0.0; network.v()];
+    for e in mf.edges() {
+        if e.flow() < -EPSILON || e.flow() > e.capacity() + EPSILON {
+            return false;
+        }
+        excess[e.from()] -= e.flow();
+        excess[e.to()] += e.flow();
+    }
+    let conserved = (0..network.v())
+        .filter(|&v| v != mf.source() && v != mf.sink())
+        .all(|v| excess[v].abs() < EPSILON);
+
+    // the value equals the capacity of the cut
+    let cut: f64 = mf
+        .edges()
+        .iter()
+        .filter(|e| mf.in_cut(e.from()) && !mf.in_cut(e.to()))
+        .map(|e| e.capacity())
+        .sum();
+
+    conserved &&
+        (excess[mf.sink()] - mf.value()).abs() < EPSILON &&
+        (cut - mf.value()).abs() < EPSILON &&
+        mf.in_cut(mf.source()) &&
+        !mf.in_cut(mf.sink())
+}
+
+#[test]
+fn test_max_flow() {
+    let mut g = FlowNetwork::new(6);
+    for &(v, w, c) in &[
+        (0, 1, 2.0),
+        (0, 2, 3.0),
+        (1, 3, 3.0),
+        (1, 4, 1.0),
+        (2, 3, 1.0),
+        (2, 4, 1.0),
+        (3, 5, 2.0),
+        (4, 5, 3.0),
+    ] {
+        g.add_edge(FlowEdge::new(v, w, c));
+    }
+    assert_eq!(6, g.v());
+    assert_eq!(8, g.e());
+    assert_eq!(3, g.adj(1).len());
+
+    for mf in vec![g.edmonds_karp(0, 5), g.dinic(0, 5)] {
+        assert_eq!(4.0, mf.value());
+        assert_eq!(vec![0, 2], mf.min_cut());
+        assert!(check_max_flow(&g, &mf));
+    }
+
+    // the network itself is left untouched
+    assert!(g.all_edges().iter().all(|e| e.flow() == 0.0));
+}
+
+#[test]
+fn test_max_flow_assignment() {
+    // workers 1..=3, tasks 4..=6, source 0 and sink 7
+    let mut g = FlowNetwork::new(8);
+    for &(worker, task) in &[(1, 4), (1, 5), (2, 4), (3, 4), (3, 6)] {
+        g.add_edge(FlowEdge::new(worker, task, 1.0));
+    }
+    for i in 1..4 {
+        g.add_edge(FlowEdge::new(0, i, 1.0));
+        g.add_edge(FlowEdge::new(i + 3, 7, 1.0));
+    }
+
+    let mf = g.dinic(0, 7);
+    assert_eq!(3.0, mf.value());
+    let assigned = mf
+        .edges()
+        .iter()
+        .filter(|e| e.from() != 0 && e.to() != 7 && e.flow() == 1.0)
+        .map(|e| (e.from(), e.to()))
+        .collect::<Vec<_>>();
+    assert_eq!(3, assigned.len());
+    assert!(assigned.contains(&(2, 4)));
+}
+
+#[test]
+fn quicktest_max_flow() {
+    fn prop(n: u8, edges: Vec<(u8, u8, u8)>) -> bool {
+        let n = n as usize % 10 + 2;
+        let mut g = FlowNetwork::new(n);
+        for (v, w, c) in edges {
+            g.add_edge(FlowEdge::new(v as usize % n, w as usize % n, (c % 16) as f64));
+        }
+        let ek = g.edmonds_karp(0, n - 1);
+        let dinic = g.dinic(0, n - 1);
+        ek.value() == dinic.value() && check_max_flow(&g, &ek) && check_max_flow(&g, &dinic)
+    }
+
+    quickcheck(prop as fn(u8, Vec<(u8, u8, u8)>) -> bool);
+}
@@ -0,0 +1,461 @@
+//! Random and regular families of graphs, for examples and property tests.
+//!
+//! The random generators take any `Rng`, so that a seeded `StdRng` reproduces the same graph:
+//!
+//! ```ignore
+//! let mut rng = StdRng::seed_from_u64(42);
+//! let g = generators::gnp(100, 0.05, &mut rng);
+//! ```
+use super::super::priority_queue::MinPQ;
+use super::{Digraph, Graph};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::iter;
+
+#[cfg(any(test, feature = "quickcheck"))]
+use quickcheck::{Arbitrary, Gen};
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+#[cfg(test)]
+use rand::rngs::StdRng;
+#[cfg(test)]
+use rand::SeedableRng;
+
+/// `e` distinct unordered pairs of distinct vertices out of `0..v`, by rejection.
+fn sample_pairs<R: Rng + ?Sized>(v: usize, e: usize, rng: &mut R) -> Vec<(usize, usize)> {
+    assert!(v < 2 || e <= v * (v - 1) / 2, "too many edges");
+    assert!(v >= 2 || e == 0, "too many edges");
+    let mut seen = HashSet::with_capacity(e);
+    let mut pairs = Vec::with_capacity(e);
+    while pairs.len() < e {
+        let (a, b) = (rng.gen_range(0, v), rng.gen_range(0, v));
+        if a != b && seen.insert((a.min(b), a.max(b))) {
+            pairs.push((a, b));
+        }
+    }
+    pairs
+}
+
+/// Erdős–Rényi G(n, m): a simple graph on `v` vertices with `e` edges chosen uniformly at random.
+pub fn gnm<R: Rng + ?Sized>(v: usize, e: usize, rng: &mut R) -> Graph {
+    let mut g = Graph::new(v);
+    for (a, b) in sample_pairs(v, e, rng) {
+        g.add_edge(a, b);
+    }
+    g
+}
+
+/// Erdős–Rényi G(n, p): a simple graph on `v` vertices where each of the possible edges is present with
+/// probability `p`.
+pub fn gnp<R: Rng + ?Sized>(v: usize, p: f64, rng: &mut R) -> Graph {
+    let mut g = Graph::new(v);
+    for a in 0..v {
+        for b in a + 1..v {
+            if rng.gen_bool(p) {
+                g.add_edge(a, b);
+            }
+        }
+    }
+    g
+}
+
+/// A simple digraph on `v` vertices with `e` edges chosen uniformly at random.
+pub fn digraph_gnm<R: Rng + ?Sized>(v: usize, e: usize, rng: &mut R) -> Digraph {
+    assert!(e <= v * v.saturating_sub(1), "too many edges");
+    let mut g = Digraph::new(v);
+    let mut seen = HashSet::with_capacity(e);
+    while seen.len() < e {
+        let (a, b) = (rng.gen_range(0, v), rng.gen_range(0, v));
+        if a != b && seen.insert((a, b)) {
+            g.add_edge(a, b);
+        }
+    }
+    g
+}
+
+/// A simple digraph on `v` vertices where each of the possible edges is present with probability `p`.
+pub fn digraph_gnp<R: Rng + ?Sized>(v: usize, p: f64, rng: &mut R) -> Digraph {
+    let mut g = Digraph::new(v);
+    for a in 0..v {
+        for b in 0..v {
+            if a != b && rng.gen_bool(p) {
+                g.add_edge(a, b);
+            }
+        }
+    }
+    g
+}
+
+/// A random DAG on `v` vertices with `e` edges, all pointing forward in a random order of the vertices.
+pub fn dag<R: Rng + ?Sized>(v: usize, e: usize, rng: &mut R) -> Digraph {
+    let mut order = (0..v).collect::<Vec<usize>>();
+    order.shuffle(rng);
+    let mut g = Digraph::new(v);
+    for (a, b) in sample_pairs(v, e, rng) {
+        g.add_edge(order[a.min(b)], order[a.max(b)]);
+    }
+    g
+}
+
+/// A tournament on `v` vertices: every pair of vertices joined by one edge in a random direction.
+pub fn tournament<R: Rng + ?Sized>(v: usize, rng: &mut R) -> Digraph {
+    let mut g = Digraph::new(v);
+    for a in 0..v {
+        for b in a + 1..v {
+            if rng.gen() {
+                g.add_edge(a, b);
+            } else {
+                g.add_edge(b, a);
+            }
+        }
+    }
+    g
+}
+
+/// A random bipartite graph with `e` edges between vertices `0..v1` and `v1..v1 + v2`.
+pub fn bipartite_gnm<R: Rng + ?Sized>(v1: usize, v2: usize, e: usize, rng: &mut R) -> Graph {
+    assert!(e <= v1 * v2, "too many edges");
+    let mut g = Graph::new(v1 + v2);
+    let mut seen = HashSet::with_capacity(e);
+    while seen.len() < e {
+        let (a, b) = (rng.gen_range(0, v1), v1 + rng.gen_range(0, v2));
+        if seen.insert((a, b)) {
+            g.add_edge(a, b);
+        }
+    }
+    g
+}
+
+/// A random bipartite graph between vertices `0..v1` and `v1..v1 + v2`, each of the possible edges present with
+/// probability `p`.
+pub fn bipartite_gnp<R: Rng + ?Sized>(v1: usize, v2: usize, p: f64, rng: &mut R) -> Graph {
+    let mut g = Graph::new(v1 + v2);
+    for a in 0..v1 {
+        for b in v1..v1 + v2 {
+            if rng.gen_bool(p) {
+                g.add_edge(a, b);
+            }
+        }
+    }
+    g
+}
+
+/// Pair up `k` stubs per vertex, never joining a vertex to itself or to a neighbour twice. Gives up when the
+/// remaining stubs can no longer be paired.
+fn try_regular<R: Rng + ?Sized>(v: usize, k: usize, rng: &mut R) -> Option<Graph> {
+    let mut stubs = (0..v).flat_map(|x| iter::repeat(x).take(k)).collect::<Vec<usize>>();
+    let mut edges = HashSet::with_capacity(v * k / 2);
+    let mut g = Graph::new(v);
+    while !stubs.is_empty() {
+        let suitable =
+            |edges: &HashSet<(usize, usize)>, a: usize, b: usize| a != b && !edges.contains(&(a.min(b), a.max(b)));
+        let mut pick = None;
+        for _ in 0..stubs.len() {
+            let (i, j) = (rng.gen_range(0, stubs.len()), rng.gen_range(0, stubs.len()));
+            if suitable(&edges, stubs[i], stubs[j]) {
+                pick = Some((i, j));
+                break;
+            }
+        }
+        if pick.is_none() {
+            let n = stubs.len();
+            pick = (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .find(|&(i, j)| suitable(&edges, stubs[i], stubs[j]));
+        }
+        let (i, j) = pick?;
+        let (a, b) = (stubs[i], stubs[j]);
+        edges.insert((a.min(b), a.max(b)));
+        g.add_edge(a, b);
+        stubs.swap_remove(i.max(j));
+        stubs.swap_remove(i.min(j));
+    }
+    Some(g)
+}
+
+/// A random simple `k`-regular graph on `v` vertices.
+///
+/// Stubs are paired at random while avoiding self-loops and parallel edges, starting over whenever the pairing
+/// gets stuck, which is rare unless `k` is close to `v`.
+pub fn regular<R: Rng + ?Sized>(v: usize, k: usize, rng: &mut R) -> Graph {
+    assert!(k < v || v == 0, "degree must be less than the number of vertices");
+    assert!((v * k) & 1 == 0, "number of vertices times degree must be even");
+    loop {
+        if let Some(g) = try_regular(v, k, rng) {
+            return g;
+        }
+    }
+}
+
+/// A uniformly random labelled tree on `v` vertices, decoded from a random Prüfer sequence.
+pub fn tree<R: Rng + ?Sized>(v: usize, rng: &mut R) -> Graph {
+    let mut g = Graph::new(v);
+    if v < 2 {
+        return g;
+    }
+    let prufer = (0..v - 2).map(|_| rng.gen_range(0, v)).collect::<Vec<usize>>();
+    let mut degree = iter::repeat(1).take(v).collect::<Vec<usize>>();
+    for &x in &prufer {
+        degree[x] += 1;
+    }
+    let mut leaves = MinPQ::new();
+    for (x, _) in degree.iter().enumerate().filter(|&(_, &d)| d == 1) {
+        leaves.insert(x);
+    }
+    for &x in &prufer {
+        let leaf = leaves.del_min().unwrap();
+        g.add_edge(leaf, x);
+        degree[x] -= 1;
+        if degree[x] == 1 {
+            leaves.insert(x);
+        }
+    }
+    let a = leaves.del_min().unwrap();
+    let b = leaves.del_min().unwrap();
+    g.add_edge(a, b);
+    g
+}
+
+/// The `rows` by `cols` grid, vertex `r * cols + c` joined to its neighbours right and below.
+pub fn grid(rows: usize, cols: usize) -> Graph {
+    let mut g = Graph::new(rows * cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let v = r * cols + c;
+            if c + 1 < cols {
+                g.add_edge(v, v + 1);
+            }
+            if r + 1 < rows {
+                g.add_edge(v, v + cols);
+            }
+        }
+    }
+    g
+}
+
+/// The complete graph on `v` vertices.
+pub fn complete(v: usize) -> Graph {
+    let mut g = Graph::new(v);
+    for a in 0..v {
+        for b in a + 1..v {
+            g.add_edge(a, b);
+        }
+    }
+    g
+}
+
+/// The complete bipartite graph between vertices `0..v1` and `v1..v1 + v2`.
+pub fn complete_bipartite(v1: usize, v2: usize) -> Graph {
+    let mut g = Graph::new(v1 + v2);
+    for a in 0..v1 {
+        for b in v1..v1 + v2 {
+            g.add_edge(a, b);
+        }
+    }
+    g
+}
+
+/// The path `0 - 1 - ... - v-1`.
+pub fn path(v: usize) -> Graph {
+    let mut g = Graph::new(v);
+    for a in 1..v {
+        g.add_edge(a - 1, a);
+    }
+    g
+}
+
+/// The cycle `0 - 1 - ... - v-1 - 0`. A cycle on one vertex is a self-loop, on two a pair of parallel edges.
+pub fn cycle(v: usize) -> Graph {
+    let mut g = path(v);
+    if v > 0 {
+        g.add_edge(v - 1, 0);
+    }
+    g
+}
+
+/// The directed path `0 -> 1 -> ... -> v-1`.
+pub fn directed_path(v: usize) -> Digraph {
+    let mut g = Digraph::new(v);
+    for a in 1..v {
+        g.add_edge(a - 1, a);
+    }
+    g
+}
+
+/// The directed cycle `0 -> 1 -> ... -> v-1 -> 0`.
+pub fn directed_cycle(v: usize) -> Digraph {
+    let mut g = directed_path(v);
+    if v > 0 {
+        g.add_edge(v - 1, 0);
+    }
+    g
+}
+
+/// Arbitrary graphs with up to `size` vertices and edges, self-loops and parallel edges included. Shrinks by
+/// removing the last vertex or a single edge.
+#[cfg(any(test, feature = "quickcheck"))]
+impl Arbitrary for Graph {
+    fn arbitrary<G: Gen>(g: &mut G) -> Graph {
+        let size = g.size();
+        let v = g.gen_range(0, size + 1);
+        let mut graph = Graph::new(v);
+        if v > 0 {
+            for _ in 0..g.gen_range(0, size + 1) {
+                graph.add_edge(g.gen_range(0, v), g.gen_range(0, v));
+            }
+        }
+        graph
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Graph>> {
+        let without_vertex = if self.vertices() > 0 {
+            let mut g = self.clone();
+            g.remove_vertex(self.vertices() - 1);
+            Some(g)
+        } else {
+            None
+        };
+        let graph = self.clone();
+        let without_edge = self.all_edges().into_iter().map(move |(a, b)| {
+            let mut g = graph.clone();
+            g.remove_edge(a, b);
+            g
+        });
+        Box::new(without_vertex.into_iter().chain(without_edge))
+    }
+}
+
+/// Arbitrary digraphs with up to `size` vertices and edges, self-loops and parallel edges included. Shrinks by
+/// removing the last vertex or a single edge.
+#[cfg(any(test, feature = "quickcheck"))]
+impl Arbitrary for Digraph {
+    fn arbitrary<G: Gen>(g: &mut G) -> Digraph {
+        let size = g.size();
+        let v = g.gen_range(0, size + 1);
+        let mut graph = Digraph::new(v);
+        if v > 0 {
+            for _ in 0..g.gen_range(0, size + 1) {
+                graph.add_edge(g.gen_range(0, v), g.gen_range(0, v));
+            }
+        }
+        graph
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Digraph>> {
+        let without_vertex = if self.v() > 0 {
+            let mut g = self.clone();
+            g.remove_vertex(self.v() - 1);
+            Some(g)
+        } else {
+            None
+        };
+        let graph = self.clone();
+        let without_edge = self.all_edges().into_iter().map(move |(a, b)| {
+            let mut g = graph.clone();
+            g.remove_edge(a, b);
+            g
+        });
+        Box::new(without_vertex.into_iter().chain(without_edge))
+    }
+}
+
+#[cfg(test)]
+fn is_simple(edges: &[(usize, usize)]) -> bool {
+    let mut seen = HashSet::new();
+    edges.iter().all(|&(a, b)| a != b && seen.insert((a.min(b), a.max(b))))
+}
+
+#[test]
+fn test_random_graphs() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let g = gnm(50, 200, &mut rng);
+    assert_eq!((50, 200), (g.vertices(), g.edges()));
+    assert!(is_simple(&g.all_edges()));
+    assert_eq!(complete(6).all_edges().len(), gnm(6, 15, &mut rng).edges());
+
+    let g = gnp(200, 0.1, &mut rng);
+    assert!(g.edges() > 1500 && g.edges() < 2500);
+    assert!(is_simple(&g.all_edges()));
+    assert_eq!(0, gnp(20, 0.0, &mut rng).edges());
+    assert_eq!(190, gnp(20, 1.0, &mut rng).edges());
+
+    let g = digraph_gnm(30, 100, &mut rng);
+    assert_eq!((30, 100), (g.v(), g.e()));
+    let edges = g.all_edges();
+    assert!(edges.iter().all(|&(a, b)| a != b));
+    assert_eq!(100, edges.iter().collect::<HashSet<_>>().len());
+    assert_eq!(20 * 19, digraph_gnp(20, 1.0, &mut rng).e());
+
+    let g = dag(40, 300, &mut rng);
+    assert_eq!(300, g.e());
+    assert!(g.topological_order().is_ok());
+
+    let g = tournament(10, &mut rng);
+    assert_eq!(45, g.e());
+    assert!(is_simple(&g.all_edges()));
+
+    let g = bipartite_gnm(10, 15, 60, &mut rng);
+    assert_eq!(60, g.edges());
+    let (left, right) = g.bipartition().unwrap();
+    assert!(left.iter().all(|&v| v < 10) || right.iter().all(|&v| v < 10));
+    assert!(bipartite_gnp(10, 15, 0.5, &mut rng).bipartition().is_ok());
+}
+
+#[test]
+fn test_regular_and_tree() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for &(v, k) in &[(10, 3), (20, 4), (7, 6), (12, 0), (0, 0)] {
+        let g = regular(v, k, &mut rng);
+        assert_eq!(v * k / 2, g.edges());
+        assert!((0..v).all(|x| g.degree(x) == k));
+        assert!(is_simple(&g.all_edges()));
+    }
+
+    for v in 0..30 {
+        let g = tree(v, &mut rng);
+        assert_eq!(v.saturating_sub(1), g.edges());
+        assert!(!g.cycle().has_cycle());
+        assert!(v == 0 || g.connected_components().count() == 1);
+    }
+}
+
+#[test]
+fn test_regular_families() {
+    let g = grid(3, 4);
+    assert_eq!((12, 17), (g.vertices(), g.edges()));
+    assert_eq!(Some(6), g.bfs(0).path_to(11).map(|p| p.len()));
+
+    assert_eq!(10, complete(5).edges());
+    assert!(complete(5)
+        .adjacency_matrix()
+        .iter()
+        .enumerate()
+        .all(|(i, row)| row.iter().sum::<usize>() == 4 && row[i] == 0));
+    assert_eq!(12, complete_bipartite(3, 4).edges());
+    assert!(complete_bipartite(3, 4).bipartition().is_ok());
+
+    assert_eq!(4, path(5).edges());
+    assert!(!path(5).cycle().has_cycle());
+    assert_eq!(5, cycle(5).edges());
+    assert!(cycle(5).bipartition().is_err());
+    assert_eq!(0, cycle(0).edges());
+
+    assert!(directed_path(5).topological_order().is_ok());
+    assert_eq!(Some(6), directed_cycle(5).directed_cycle().cycle().map(|c| c.len()));
+}
+
+#[test]
+fn quicktest_arbitrary_graphs() {
+    fn prop_graph(g: Graph) -> bool {
+        g.all_edges().len() == g.edges() && g.shrink().all(|h| h.vertices() + h.edges() < g.vertices() + g.edges())
+    }
+    quickcheck(prop_graph as fn(Graph) -> bool);
+
+    fn prop_digraph(g: Digraph) -> bool {
+        g.all_edges().len() == g.e() && g.shrink().all(|h| h.v() + h.e() < g.v() + g.e())
+    }
+    quickcheck(prop_digraph as fn(Digraph) -> bool);
+}
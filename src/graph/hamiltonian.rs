@@ -0,0 +1,117 @@
+use super::view::GraphView;
+use std::iter;
+
+#[cfg(test)]
+use super::{Digraph, Graph};
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// Largest graph `hamiltonian_path` accepts; the search takes `O(2^V V^2)` time and `O(2^V)` words of space.
+pub const MAX_HAMILTONIAN_VERTICES: usize = 24;
+
+/// A path visiting every vertex exactly once, by dynamic programming over the subsets of vertices: `ends[set]`
+/// holds, as a bitmask, the vertices at which some path through exactly `set` ends.
+///
+/// Panics if the graph has more than `MAX_HAMILTONIAN_VERTICES` vertices.
+pub fn hamiltonian_path<G: GraphView>(graph: &G) -> Option<Vec<usize>> {
+    let n = graph.vertex_count();
+    assert!(n <= MAX_HAMILTONIAN_VERTICES, "too many vertices for a Hamiltonian path search");
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    // into[w] has bit v set if there is an edge v -> w
+    let mut into = iter::repeat(0u32).take(n).collect::<Vec<u32>>();
+    for v in 0..n {
+        for w in graph.neighbors(v) {
+            if v != w {
+                into[w] |= 1 << v;
+            }
+        }
+    }
+
+    let full = (1usize << n) - 1;
+    let mut ends = iter::repeat(0u32).take(full + 1).collect::<Vec<u32>>();
+    for v in 0..n {
+        ends[1 << v] = 1 << v;
+    }
+    for set in 1..full {
+        if ends[set] == 0 {
+            continue;
+        }
+        for w in (0..n).filter(|&w| set & (1 << w) == 0) {
+            if into[w] & ends[set] != 0 {
+                ends[set | (1 << w)] |= 1 << w;
+            }
+        }
+    }
+    if ends[full] == 0 {
+        return None;
+    }
+
+    // walk back from any end, each time to a vertex that ends a path through the remaining set
+    let mut path = Vec::with_capacity(n);
+    let mut set = full;
+    let mut v = ends[full].trailing_zeros() as usize;
+    loop {
+        path.push(v);
+        set &= !(1 << v);
+        if set == 0 {
+            break;
+        }
+        v = (ends[set] & into[v]).trailing_zeros() as usize;
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+fn is_hamiltonian_path<F: Fn(usize, usize) -> bool>(n: usize, path: &[usize], has_edge: F) -> bool {
+    let mut sorted = path.to_vec();
+    sorted.sort();
+    sorted == (0..n).collect::<Vec<usize>>() && path.windows(2).all(|e| has_edge(e[0], e[1]))
+}
+
+#[test]
+fn test_hamiltonian_path() {
+    let g: Graph = "5 5\n0 1\n1 2\n2 0\n0 3\n3 4".parse().unwrap();
+    let path = g.hamiltonian_path().unwrap();
+    assert!(is_hamiltonian_path(5, &path, |v, w| g.has_edge(v, w)));
+
+    // a star has none
+    let g: Graph = "4 3\n0 1\n0 2\n0 3".parse().unwrap();
+    assert_eq!(None, g.hamiltonian_path());
+
+    let d: Digraph = "4 4\n3 1\n1 2\n2 0\n0 1".parse().unwrap();
+    assert_eq!(Some(vec![3, 1, 2, 0]), d.hamiltonian_path());
+    let d: Digraph = "3 2\n0 1\n2 1".parse().unwrap();
+    assert_eq!(None, d.hamiltonian_path());
+
+    assert_eq!(Some(vec![]), Graph::new(0).hamiltonian_path());
+    assert_eq!(Some(vec![0]), Graph::new(1).hamiltonian_path());
+    assert_eq!(None, Graph::new(2).hamiltonian_path());
+}
+
+#[test]
+fn quicktest_hamiltonian_path() {
+    // a tournament always has a Hamiltonian path
+    fn prop(n: u8, coins: Vec<bool>) -> bool {
+        let n = n as usize % 12;
+        let mut g = Digraph::new(n);
+        let mut coins = coins.into_iter().cycle();
+        for v in 0..n {
+            for w in v + 1..n {
+                if coins.next().unwrap_or(true) {
+                    g.add_edge(v, w);
+                } else {
+                    g.add_edge(w, v);
+                }
+            }
+        }
+        match g.hamiltonian_path() {
+            Some(path) => is_hamiltonian_path(n, &path, |v, w| g.has_edge(v, w)),
+            None => false,
+        }
+    }
+    quickcheck(prop as fn(u8, Vec<bool>) -> bool);
+}
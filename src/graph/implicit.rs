@@ -0,0 +1,354 @@
+//! Searches on graphs given by a successor function instead of adjacency lists, such as the positions of a
+//! puzzle or the free cells of a map, which are found as the search goes and never stored as a `Digraph`.
+//!
+//! Nodes can be any `Eq + Hash + Clone` value, and costs anything ordered that adds up, starting from
+//! `C::default()` as zero: integers, or `f64` as long as no cost is NaN.
+use super::super::priority_queue::MinPQ;
+use super::super::stack::Stack;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+use std::ops::Add;
+
+#[cfg(test)]
+use super::super::primitive::Point2D;
+#[cfg(test)]
+use super::Graph;
+
+/// Follow `parent` links from `v` back to the root, then reverse.
+fn unwind<N: Clone>(nodes: &[N], parent: &[Option<usize>], v: usize) -> Vec<N> {
+    let mut path = Vec::new();
+    let mut x = Some(v);
+    while let Some(i) = x {
+        path.push(nodes[i].clone());
+        x = parent[i];
+    }
+    path.reverse();
+    path
+}
+
+/// A* search from `start` to the first node satisfying `success`, returning the path and its cost.
+///
+/// `successors` lists the neighbours of a node with the cost of the edge to each, and `heuristic` estimates the
+/// remaining cost to a goal. The path is a cheapest one when the heuristic never overestimates; a node is searched
+/// again whenever a cheaper way to it turns up, so the heuristic need not be consistent. The frontier is a
+/// `MinPQ` ordered by estimated total cost, where entries made stale by a cheaper path are skipped.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut index = HashMap::new();
+    let mut nodes = Vec::new();
+    let mut parent = Vec::new();
+    let mut cost = Vec::new();
+    let mut estimate = Vec::new();
+
+    let zero = C::default();
+    index.insert(start.clone(), 0);
+    estimate.push(heuristic(&start));
+    nodes.push(start);
+    parent.push(None);
+    cost.push(zero);
+
+    let mut pq = MinPQ::new();
+    pq.insert((estimate[0], zero, 0));
+    while !pq.is_empty() {
+        let (_, g, v) = pq.del_min().unwrap();
+        if g > cost[v] {
+            continue;
+        }
+        if success(&nodes[v]) {
+            return Some((unwind(&nodes, &parent, v), g));
+        }
+        for (w, c) in successors(&nodes[v]) {
+            let gw = g + c;
+            let i = match index.get(&w) {
+                Some(&i) if gw < cost[i] => i,
+                Some(_) => continue,
+                None => {
+                    let i = nodes.len();
+                    index.insert(w.clone(), i);
+                    estimate.push(heuristic(&w));
+                    nodes.push(w);
+                    parent.push(None);
+                    cost.push(gw);
+                    i
+                }
+            };
+            parent[i] = Some(v);
+            cost[i] = gw;
+            pq.insert((gw + estimate[i], gw, i));
+        }
+    }
+    None
+}
+
+/// Iterative-deepening A*: depth-first searches that give up on paths whose estimated total cost exceeds a bound,
+/// raising the bound to the smallest cost that went over it each round. Same answer as `astar` with memory in
+/// the length of the path only, at the price of searching nodes again; paths never revisit their own nodes.
+pub fn ida_star<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Clone,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let zero = C::default();
+    if success(&start) {
+        return Some((vec![start], zero));
+    }
+    let mut bound = heuristic(&start);
+    loop {
+        let mut next_bound: Option<C> = None;
+        let mut path = vec![start.clone()];
+        let mut stack = Stack::new();
+        stack.push((zero, successors(&start).into_iter()));
+        while let Some(frame) = stack.peek_mut() {
+            let g = frame.0;
+            match frame.1.next() {
+                Some((w, c)) => {
+                    if path.contains(&w) {
+                        continue;
+                    }
+                    let gw = g + c;
+                    let f = gw + heuristic(&w);
+                    if f > bound {
+                        next_bound = match next_bound {
+                            Some(b) if b <= f => Some(b),
+                            _ => Some(f),
+                        };
+                        continue;
+                    }
+                    if success(&w) {
+                        path.push(w);
+                        return Some((path, gw));
+                    }
+                    stack.push((gw, successors(&w).into_iter()));
+                    path.push(w);
+                }
+                None => {
+                    stack.pop();
+                    path.pop();
+                }
+            }
+        }
+        bound = next_bound?;
+    }
+}
+
+/// Breadth-first search from both ends at once, growing the smaller frontier one level at a time until the two
+/// meet. Returns a path with the fewest edges and its length.
+///
+/// `successors` gives the nodes after a node and `predecessors` the nodes before it; pass the same function twice
+/// when every move can be undone.
+pub fn bidirectional_bfs<N, FN, IN, FP, IP>(
+    start: N,
+    goal: N,
+    mut successors: FN,
+    mut predecessors: FP,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FP: FnMut(&N) -> IP,
+    IP: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some((vec![start], 0));
+    }
+    // parent of each node seen from the start, and next node towards the goal of each node seen from the goal
+    let mut forward: HashMap<N, Option<N>> = HashMap::new();
+    let mut backward: HashMap<N, Option<N>> = HashMap::new();
+    forward.insert(start.clone(), None);
+    backward.insert(goal.clone(), None);
+    let mut forward_frontier = vec![start];
+    let mut backward_frontier = vec![goal];
+
+    let meet = 'search: loop {
+        if forward_frontier.is_empty() || backward_frontier.is_empty() {
+            return None;
+        }
+        let expand_forward = forward_frontier.len() <= backward_frontier.len();
+        let (frontier, seen, other) = if expand_forward {
+            (&mut forward_frontier, &mut forward, &backward)
+        } else {
+            (&mut backward_frontier, &mut backward, &forward)
+        };
+        let mut next = Vec::new();
+        for v in mem::take(frontier) {
+            let neighbors = if expand_forward {
+                successors(&v).into_iter().collect::<Vec<N>>()
+            } else {
+                predecessors(&v).into_iter().collect()
+            };
+            for w in neighbors {
+                if seen.contains_key(&w) {
+                    continue;
+                }
+                seen.insert(w.clone(), Some(v.clone()));
+                if other.contains_key(&w) {
+                    break 'search w;
+                }
+                next.push(w);
+            }
+        }
+        *frontier = next;
+    };
+
+    let mut path = vec![meet.clone()];
+    let mut x = meet.clone();
+    while let Some(Some(p)) = forward.get(&x) {
+        path.push(p.clone());
+        x = p.clone();
+    }
+    path.reverse();
+    let mut x = meet;
+    while let Some(Some(n)) = backward.get(&x) {
+        path.push(n.clone());
+        x = n.clone();
+    }
+    let len = path.len() - 1;
+    Some((path, len))
+}
+
+/// A map of free (`.`) and blocked (`#`) cells, searched with moves to the eight surrounding free cells.
+#[cfg(test)]
+fn grid_moves(map: &[&str], (x, y): (usize, usize)) -> Vec<((usize, usize), f64)> {
+    let free = |x: isize, y: isize| {
+        y >= 0 && (y as usize) < map.len() && x >= 0 && map[y as usize].as_bytes().get(x as usize) == Some(&b'.')
+    };
+    let mut moves = Vec::new();
+    for &(dx, dy) in &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        // no cutting corners
+        if free(nx, ny) && free(nx, y as isize) && free(x as isize, ny) {
+            let to = (nx as usize, ny as usize);
+            moves.push((to, Point2D::new(x as f64, y as f64).distance_to(point(to))));
+        }
+    }
+    moves
+}
+
+#[cfg(test)]
+fn point((x, y): (usize, usize)) -> Point2D {
+    Point2D::new(x as f64, y as f64)
+}
+
+#[test]
+fn test_astar_grid() {
+    let map = [
+        "..........",
+        ".######...",
+        "......#...",
+        "#####.#.##",
+        "......#...",
+        ".######...",
+        "..........",
+    ];
+    let (start, goal) = ((0, 2), (9, 4));
+    let h = |&p: &(usize, usize)| point(p).distance_to(point(goal));
+
+    let (path, cost) = astar(start, |&p| grid_moves(&map, p), h, |&p| p == goal).unwrap();
+    assert_eq!((Some(&start), Some(&goal)), (path.first(), path.last()));
+    assert!(path
+        .windows(2)
+        .all(|s| grid_moves(&map, s[0]).iter().any(|m| m.0 == s[1])));
+    let length = path
+        .windows(2)
+        .map(|s| point(s[0]).distance_to(point(s[1])))
+        .sum::<f64>();
+    assert!((length - cost).abs() < 1e-9);
+
+    // without a heuristic, A* is Dijkstra's algorithm
+    let (_, dijkstra) = astar(start, |&p| grid_moves(&map, p), |_| 0.0, |&p| p == goal).unwrap();
+    assert!((dijkstra - cost).abs() < 1e-9);
+
+    let (ida_path, ida_cost) = ida_star(start, |&p| grid_moves(&map, p), h, |&p| p == goal).unwrap();
+    assert!((ida_cost - cost).abs() < 1e-9);
+    assert_eq!(Some(&goal), ida_path.last());
+
+    let moves = |&p: &(usize, usize)| grid_moves(&map, p).into_iter().map(|m| m.0);
+    let (bfs_path, steps) = bidirectional_bfs(start, goal, moves, moves).unwrap();
+    assert_eq!(steps + 1, bfs_path.len());
+    assert_eq!((Some(&start), Some(&goal)), (bfs_path.first(), bfs_path.last()));
+    let fewest = astar(start, |&p| moves(&p).map(|q| (q, 1)).collect::<Vec<_>>(), |_| 0, |&p| p == goal);
+    assert_eq!(Some(steps), fewest.map(|r| r.1));
+
+    // walled in
+    let map = ["...#....", "...#....", "...#...."];
+    let goal = (7, 0);
+    assert!(astar((0, 0), |&p| grid_moves(&map, p), |_| 0.0, |&p| p == goal).is_none());
+    assert!(ida_star((0, 0), |&p| grid_moves(&map, p), |_| 0.0, |&p| p == goal).is_none());
+    let moves = |&p: &(usize, usize)| grid_moves(&map, p).into_iter().map(|m| m.0);
+    assert!(bidirectional_bfs((0, 0), goal, moves, moves).is_none());
+}
+
+#[test]
+fn test_search_integer_costs() {
+    // the 8-puzzle: slide tiles into the blank (0) until they are in order
+    fn slides(board: &[u8; 9]) -> Vec<([u8; 9], u32)> {
+        let blank = board.iter().position(|&t| t == 0).unwrap();
+        let (r, c) = (blank / 3, blank % 3);
+        let mut next = Vec::new();
+        let mut slide = |to: usize| {
+            let mut b = *board;
+            b.swap(blank, to);
+            next.push((b, 1));
+        };
+        if r > 0 {
+            slide(blank - 3);
+        }
+        if r < 2 {
+            slide(blank + 3);
+        }
+        if c > 0 {
+            slide(blank - 1);
+        }
+        if c < 2 {
+            slide(blank + 1);
+        }
+        next
+    }
+    fn manhattan(board: &[u8; 9]) -> u32 {
+        let mut sum = 0;
+        for (i, &t) in board.iter().enumerate().filter(|&(_, &t)| t != 0) {
+            let goal = t as usize - 1;
+            sum += ((i / 3) as i32 - (goal / 3) as i32).abs() + ((i % 3) as i32 - (goal % 3) as i32).abs();
+        }
+        sum as u32
+    }
+    let solved = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+    let start = [8, 1, 3, 4, 0, 2, 7, 6, 5];
+
+    let (path, moves) = astar(start, slides, manhattan, |b| *b == solved).unwrap();
+    assert_eq!(path.len() as u32 - 1, moves);
+    let (_, ida_moves) = ida_star(start, slides, manhattan, |b| *b == solved).unwrap();
+    assert_eq!(moves, ida_moves);
+    let boards = |b: &[u8; 9]| slides(b).into_iter().map(|s| s.0);
+    assert_eq!(Some(moves as usize), bidirectional_bfs(start, solved, boards, boards).map(|r| r.1));
+
+    // a `Graph` is an implicit graph too
+    let g: Graph = "6 5\n0 1\n1 2\n2 3\n3 4\n0 4".parse().unwrap();
+    let adj = |&v: &usize| g.adj(v).cloned().collect::<Vec<usize>>();
+    assert_eq!(Some((vec![0, 4, 3], 2)), bidirectional_bfs(0, 3, adj, adj));
+    assert_eq!(None, bidirectional_bfs(0, 5, adj, adj));
+    assert_eq!(Some((vec![5], 0)), bidirectional_bfs(5, 5, adj, adj));
+}
@@ -0,0 +1,515 @@
+use super::super::queue::Queue;
+use super::super::stack::Stack;
+use super::undirected::Graph;
+use std::f64;
+use std::iter;
+use std::ops::{Add, Sub};
+
+#[cfg(test)]
+use super::flow::{FlowEdge, FlowNetwork};
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// Maximum cardinality matching of a bipartite graph, or an odd-length cycle if the graph is not bipartite.
+pub fn hopcroft_karp(graph: &Graph) -> Result<BipartiteMatching, Vec<usize>> {
+    let (left, _) = graph.bipartition()?;
+    Ok(BipartiteMatching::new(graph, &left))
+}
+
+/// A maximum cardinality matching in a bipartite graph, found by the Hopcroft-Karp algorithm.
+pub struct BipartiteMatching {
+    mate: Vec<Option<usize>>,
+    in_cover: Vec<bool>,
+    cardinality: usize,
+}
+
+impl BipartiteMatching {
+    fn new(graph: &Graph, left: &[usize]) -> BipartiteMatching {
+        let n = graph.vertices();
+        let mut is_left = iter::repeat(false).take(n).collect::<Vec<bool>>();
+        for &v in left {
+            is_left[v] = true;
+        }
+
+        let mut hk = HopcroftKarp {
+            graph: graph,
+            left: left,
+            mate: iter::repeat(None).take(n).collect(),
+            dist: iter::repeat(usize::MAX).take(n).collect(),
+        };
+        let mut cardinality = 0;
+        while hk.bfs() {
+            for &s in left {
+                if hk.mate[s].is_none() && hk.dfs(s) {
+                    cardinality += 1;
+                }
+            }
+        }
+
+        let mut matching = BipartiteMatching {
+            mate: hk.mate,
+            in_cover: Vec::new(),
+            cardinality: cardinality,
+        };
+        matching.in_cover = matching.koenig_cover(graph, &is_left);
+        matching
+    }
+
+    /// König's theorem: with `Z` the vertices reachable from free left vertices by alternating paths, the
+    /// left vertices not in `Z` together with the right vertices in `Z` are a minimum vertex cover.
+    fn koenig_cover(&self, graph: &Graph, is_left: &[bool]) -> Vec<bool> {
+        let n = graph.vertices();
+        let mut marked = iter::repeat(false).take(n).collect::<Vec<bool>>();
+        let mut q = Queue::new();
+        for v in 0..n {
+            if is_left[v] && self.mate[v].is_none() {
+                marked[v] = true;
+                q.enqueue(v);
+            }
+        }
+        while let Some(v) = q.dequeue() {
+            for &w in graph.adj(v) {
+                // non-matching edges from the left, matching edges from the right
+                let alternating = if is_left[v] {
+                    self.mate[v] != Some(w)
+                } else {
+                    self.mate[v] == Some(w)
+                };
+                if alternating && !marked[w] {
+                    marked[w] = true;
+                    q.enqueue(w);
+                }
+            }
+        }
+        (0..n).map(|v| is_left[v] != marked[v]).collect()
+    }
+
+    /// The vertex matched with `v`, if any.
+    pub fn mate(&self, v: usize) -> Option<usize> {
+        self.mate[v]
+    }
+
+    pub fn is_matched(&self, v: usize) -> bool {
+        self.mate[v].is_some()
+    }
+
+    /// Number of edges in the matching.
+    pub fn size(&self) -> usize {
+        self.cardinality
+    }
+
+    /// Is every vertex matched?
+    pub fn is_perfect(&self) -> bool {
+        2 * self.cardinality == self.mate.len()
+    }
+
+    pub fn in_min_vertex_cover(&self, v: usize) -> bool {
+        self.in_cover[v]
+    }
+
+    /// A minimum vertex cover, of the same size as the matching, certifying its optimality.
+    pub fn min_vertex_cover(&self) -> Vec<usize> {
+        (0..self.in_cover.len()).filter(|&v| self.in_cover[v]).collect()
+    }
+}
+
+struct HopcroftKarp<'a> {
+    graph: &'a Graph,
+    left: &'a [usize],
+    mate: Vec<Option<usize>>,
+    // length of the shortest alternating path from a free left vertex, for left vertices only
+    dist: Vec<usize>,
+}
+
+impl<'a> HopcroftKarp<'a> {
+    /// Layer the left vertices by alternating breadth-first search, returns whether there is an augmenting
+    /// path at all.
+    fn bfs(&mut self) -> bool {
+        let mut q = Queue::new();
+        for &v in self.left {
+            if self.mate[v].is_none() {
+                self.dist[v] = 0;
+                q.enqueue(v);
+            } else {
+                self.dist[v] = usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(v) = q.dequeue() {
+            for &w in self.graph.adj(v) {
+                match self.mate[w] {
+                    None => found = true,
+                    Some(x) if self.dist[x] == usize::MAX => {
+                        self.dist[x] = self.dist[v] + 1;
+                        q.enqueue(x);
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+        found
+    }
+
+    /// Look for an augmenting path from the free left vertex `s` along the layers, and flip it.
+    fn dfs(&mut self, s: usize) -> bool {
+        let graph = self.graph;
+        // frames of left vertices on the current alternating path, with the right vertex taken from each
+        let mut stack = Stack::new();
+        stack.push((s, graph.adj(s), None));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            let w = match frame.1.next() {
+                Some(&w) => w,
+                None => {
+                    // dead end, never try v again in this phase
+                    self.dist[v] = usize::MAX;
+                    stack.pop();
+                    continue;
+                }
+            };
+            frame.2 = Some(w);
+            match self.mate[w] {
+                None => {
+                    while let Some((v, _, w)) = stack.pop() {
+                        let w = w.unwrap();
+                        self.mate[v] = Some(w);
+                        self.mate[w] = Some(v);
+                    }
+                    return true;
+                }
+                Some(x) if self.dist[x] == self.dist[v] + 1 => {
+                    stack.push((x, graph.adj(x), None));
+                }
+                Some(_) => (),
+            }
+        }
+        false
+    }
+}
+
+/// An optimal assignment of rows to columns of a cost matrix.
+pub struct Assignment {
+    column: Vec<Option<usize>>,
+    cost: f64,
+}
+
+impl Assignment {
+    /// Minimum cost assignment by the Hungarian algorithm, in `O(n^2 m)` for `n` rows and `m` columns.
+    ///
+    /// Every row is assigned a distinct column if there are at least as many columns as rows, otherwise
+    /// every column is assigned a distinct row.
+    ///
+    /// An infinite cost forbids pairing its row and column. Rows or columns left without a permitted partner are
+    /// unassigned, as few as possible, and the cost is the minimum over assignments of that size. Panics if a cost
+    /// is NaN or negative infinity.
+    pub fn hungarian(cost: &[Vec<f64>]) -> Assignment {
+        let rows = cost.len();
+        let cols = cost.first().map_or(0, |r| r.len());
+        assert!(cost.iter().all(|r| r.len() == cols), "cost matrix is not rectangular");
+        assert!(
+            cost.iter()
+                .flat_map(|r| r.iter())
+                .all(|&c| c.is_finite() || c == f64::INFINITY),
+            "cost is NaN or negative infinity"
+        );
+
+        let mut column = iter::repeat(None).take(rows).collect::<Vec<Option<usize>>>();
+        if rows <= cols {
+            for (i, j) in hungarian(rows, cols, |i, j| cost[i][j]).into_iter().enumerate() {
+                column[i] = Some(j);
+            }
+        } else {
+            for (j, i) in hungarian(cols, rows, |j, i| cost[i][j]).into_iter().enumerate() {
+                column[i] = Some(j);
+            }
+        }
+        // forbidden pairs are only in the assignment where there was no other way to complete it
+        for (i, j) in column.iter_mut().enumerate() {
+            if let Some(c) = *j {
+                if cost[i][c] == f64::INFINITY {
+                    *j = None;
+                }
+            }
+        }
+
+        let total = column
+            .iter()
+            .enumerate()
+            .filter_map(|(i, j)| j.map(|j| cost[i][j]))
+            .sum();
+        Assignment {
+            column: column,
+            cost: total,
+        }
+    }
+
+    /// Column assigned to `row`.
+    pub fn column(&self, row: usize) -> Option<usize> {
+        self.column[row]
+    }
+
+    /// Total cost of the assignment.
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+}
+
+/// A cost ordered first by the number of forbidden pairs, then by the sum of the permitted costs. Forbidden pairs
+/// then cost more than any permitted ones while keeping the potentials finite.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+struct Cost(f64, f64);
+
+impl Cost {
+    fn of(c: f64) -> Cost {
+        if c == f64::INFINITY {
+            Cost(1.0, 0.0)
+        } else {
+            Cost(0.0, c)
+        }
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+    fn add(self, other: Cost) -> Cost {
+        Cost(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl Sub for Cost {
+    type Output = Cost;
+    fn sub(self, other: Cost) -> Cost {
+        Cost(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+/// Shortest augmenting paths with row and column potentials, for `n <= m`. Returns the column of each row, the
+/// fewest possible of them at an infinite cost.
+fn hungarian<F: Fn(usize, usize) -> f64>(n: usize, m: usize, a: F) -> Vec<usize> {
+    let zero = Cost(0.0, 0.0);
+    let unreached = Cost(f64::INFINITY, 0.0);
+    // 1-based, column 0 is a virtual column holding the row being inserted
+    let mut u = vec![zero; n + 1];
+    let mut v = vec![zero; m + 1];
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+
+    for i in 1..n + 1 {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![unreached; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = unreached;
+            let mut j1 = 0;
+            for j in 1..m + 1 {
+                if !used[j] {
+                    let cur = Cost::of(a(i0 - 1, j - 1)) - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..m + 1 {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    minv[j] = minv[j] - delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        // flip the augmenting path
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut column = vec![0; n];
+    for j in 1..m + 1 {
+        if p[j] != 0 {
+            column[p[j] - 1] = j - 1;
+        }
+    }
+    column
+}
+
+#[cfg(test)]
+fn check_matching(g: &Graph, matching: &BipartiteMatching) -> bool {
+    let mates = (0..g.vertices()).all(|v| match matching.mate(v) {
+        Some(w) => matching.mate(w) == Some(v) && g.adj(v).any(|&x| x == w),
+        None => true,
+    });
+    let matched = (0..g.vertices()).filter(|&v| matching.is_matched(v)).count();
+    let covered = (0..g.vertices()).all(|v| {
+        g.adj(v)
+            .all(|&w| matching.in_min_vertex_cover(v) || matching.in_min_vertex_cover(w))
+    });
+    mates && matched == 2 * matching.size() && covered && matching.min_vertex_cover().len() == matching.size()
+}
+
+#[test]
+fn test_hopcroft_karp() {
+    // workers 0..4, tasks 5..9
+    let mut g = Graph::new(10);
+    for &(v, w) in &[(0, 5), (0, 6), (1, 5), (2, 6), (2, 7), (2, 8), (3, 7), (4, 7), (4, 9)] {
+        g.add_edge(v, w);
+    }
+    let matching = g.hopcroft_karp().unwrap();
+    assert_eq!(5, matching.size());
+    assert!(matching.is_perfect());
+    assert_eq!(Some(3), matching.mate(7));
+    assert_eq!(Some(9), matching.mate(4));
+    assert!(check_matching(&g, &matching));
+
+    // three workers competing for two tasks, and an isolated vertex
+    let mut g = Graph::new(6);
+    for &(v, w) in &[(0, 3), (1, 3), (2, 3), (2, 4)] {
+        g.add_edge(v, w);
+    }
+    let matching = g.hopcroft_karp().unwrap();
+    assert_eq!(2, matching.size());
+    assert!(!matching.is_perfect());
+    assert!(!matching.is_matched(5));
+    assert_eq!(vec![2, 3], matching.min_vertex_cover());
+    assert!(check_matching(&g, &matching));
+
+    g.add_edge(0, 1);
+    assert!(g.hopcroft_karp().is_err());
+}
+
+#[test]
+fn quicktest_hopcroft_karp() {
+    fn prop(n: u8, m: u8, edges: Vec<(u8, u8)>) -> bool {
+        let (n, m) = (n as usize % 8 + 1, m as usize % 8 + 1);
+        let mut g = Graph::new(n + m);
+        let mut network = FlowNetwork::new(n + m + 2);
+        for (v, w) in edges {
+            let (v, w) = (v as usize % n, n + w as usize % m);
+            g.add_edge(v, w);
+            network.add_edge(FlowEdge::new(v, w, 1.0));
+        }
+        for v in 0..n {
+            network.add_edge(FlowEdge::new(n + m, v, 1.0));
+        }
+        for w in n..n + m {
+            network.add_edge(FlowEdge::new(w, n + m + 1, 1.0));
+        }
+
+        let matching = g.hopcroft_karp().unwrap();
+        check_matching(&g, &matching) && matching.size() as f64 == network.dinic(n + m, n + m + 1).value()
+    }
+
+    quickcheck(prop as fn(u8, u8, Vec<(u8, u8)>) -> bool);
+}
+
+#[test]
+fn test_hungarian() {
+    let cost = vec![vec![4.0, 1.0, 3.0], vec![2.0, 0.0, 5.0], vec![3.0, 2.0, 2.0]];
+    let assignment = Assignment::hungarian(&cost);
+    assert_eq!(5.0, assignment.cost());
+    assert_eq!(Some(1), assignment.column(0));
+    assert_eq!(Some(0), assignment.column(1));
+    assert_eq!(Some(2), assignment.column(2));
+
+    // more workers than tasks, someone stays idle
+    let cost = vec![vec![10.0, 19.0], vec![1.0, 9.0], vec![9.0, 1.0]];
+    let assignment = Assignment::hungarian(&cost);
+    assert_eq!(2.0, assignment.cost());
+    assert_eq!(None, assignment.column(0));
+
+    assert_eq!(0.0, Assignment::hungarian(&[]).cost());
+
+    // infinite costs forbid pairs, leaving rows or columns without a permitted partner unassigned
+    let inf = f64::INFINITY;
+    let assignment = Assignment::hungarian(&[vec![1.0, inf], vec![inf, inf]]);
+    assert_eq!((Some(0), None, 1.0), (assignment.column(0), assignment.column(1), assignment.cost()));
+    let assignment = Assignment::hungarian(&[vec![inf, 2.0], vec![inf, 1.0], vec![inf, 3.0]]);
+    assert_eq!(vec![None, Some(1), None], (0..3).map(|i| assignment.column(i)).collect::<Vec<_>>());
+    assert_eq!(0.0, Assignment::hungarian(&[vec![inf]]).cost());
+}
+
+#[test]
+fn quicktest_hungarian() {
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        let mut result = Vec::new();
+        for p in permutations(n - 1) {
+            for i in 0..n {
+                let mut q = p.clone();
+                q.insert(i, n - 1);
+                result.push(q);
+            }
+        }
+        result
+    }
+
+    fn prop(n: u8, costs: Vec<i8>) -> bool {
+        let n = n as usize % 5 + 1;
+        let cost = (0..n)
+            .map(|i| (0..n).map(|j| *costs.get(i * n + j).unwrap_or(&0) as f64).collect())
+            .collect::<Vec<Vec<f64>>>();
+        let best = permutations(n)
+            .iter()
+            .map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<f64>())
+            .fold(f64::INFINITY, f64::min);
+
+        let assignment = Assignment::hungarian(&cost);
+        let mut columns = (0..n).map(|i| assignment.column(i).unwrap()).collect::<Vec<usize>>();
+        columns.sort();
+        columns.dedup();
+        columns.len() == n && assignment.cost() == best
+    }
+
+    quickcheck(prop as fn(u8, Vec<i8>) -> bool);
+
+    // with forbidden pairs, as many rows as possible are assigned, at the least cost
+    fn prop_forbidden(n: u8, costs: Vec<i8>) -> bool {
+        let n = n as usize % 5 + 1;
+        let cost = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match *costs.get(i * n + j).unwrap_or(&0) {
+                        c if c < -64 => f64::INFINITY,
+                        c => c as f64,
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<f64>>>();
+        // any partial assignment extends to a permutation, so the best is a permutation less its forbidden pairs
+        let (size, best) = permutations(n)
+            .iter()
+            .map(|p| {
+                let allowed = (0..n).filter(|&i| cost[i][p[i]] < f64::INFINITY);
+                (allowed.clone().count(), allowed.map(|i| cost[i][p[i]]).sum::<f64>())
+            })
+            .fold((0, f64::INFINITY), |a, b| if b.0 > a.0 || (b.0 == a.0 && b.1 < a.1) { b } else { a });
+
+        let assignment = Assignment::hungarian(&cost);
+        let mut columns = (0..n).filter_map(|i| assignment.column(i)).collect::<Vec<usize>>();
+        columns.sort();
+        columns.dedup();
+        let allowed = (0..n).all(|i| assignment.column(i).map_or(true, |j| cost[i][j] < f64::INFINITY));
+        allowed && columns.len() == size && (size == 0 || assignment.cost() == best)
+    }
+
+    quickcheck(prop_forbidden as fn(u8, Vec<i8>) -> bool);
+}
@@ -1,6 +1,53 @@
+pub mod all_pairs;
+pub mod csr;
 pub mod directed;
+pub mod euler;
+pub mod flow;
+pub mod generators;
+pub mod hamiltonian;
+pub mod implicit;
+pub mod matching;
+pub mod parse;
+pub mod reachability;
+pub mod symbol;
+pub mod traversal;
 pub mod undirected;
+pub mod view;
+pub mod weighted_directed;
+pub mod weighted_undirected;
 
 // reexports
-pub use self::directed::Digraph;
+pub use self::all_pairs::AllPairsShortestPaths;
+pub use self::csr::CsrGraph;
+pub use self::directed::{Digraph, StronglyConnectedComponents};
+pub use self::euler::EulerianError;
+pub use self::flow::{FlowEdge, FlowNetwork};
+pub use self::parse::ParseGraphError;
+pub use self::reachability::{CondensedClosure, TransitiveClosure};
+pub use self::symbol::{SymbolDigraph, SymbolGraph};
+pub use self::traversal::{DepthFirstOrder, SearchPaths};
 pub use self::undirected::Graph;
+pub use self::view::GraphView;
+pub use self::weighted_directed::{DirectedEdge, EdgeWeightedDigraph};
+pub use self::weighted_undirected::{Edge, EdgeWeightedGraph};
+
+pub enum SearchSource {
+    Single(usize),
+    Multi(Vec<usize>),
+}
+
+impl SearchSource {
+    fn iter(&self) -> ::std::vec::IntoIter<usize> {
+        match *self {
+            SearchSource::Single(ref i) => vec![*i].into_iter(),
+            SearchSource::Multi(ref vs) => vs.clone().into_iter(),
+        }
+    }
+
+    fn contains(&self, v: usize) -> bool {
+        match *self {
+            SearchSource::Single(ref i) => *i == v,
+            SearchSource::Multi(ref vs) => vs.contains(&v),
+        }
+    }
+}
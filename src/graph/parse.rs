@@ -0,0 +1,548 @@
+use super::super::bag::Bag;
+use super::super::hashst::HashST;
+use super::directed::Digraph;
+use super::undirected::Graph;
+use super::weighted_directed::{DirectedEdge, EdgeWeightedDigraph};
+use super::weighted_undirected::{Edge, EdgeWeightedGraph};
+use std::error;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::str::FromStr;
+
+mod graphml;
+mod json;
+
+/// What went wrong while reading a graph.
+#[derive(Debug)]
+pub enum ParseGraphErrorKind {
+    Io(io::Error),
+    /// The input ended before the vertex count, the edge count or an endpoint.
+    UnexpectedEof,
+    /// A token is not a nonnegative integer.
+    InvalidNumber(String),
+    VertexOutOfRange {
+        vertex: usize,
+        vertices: usize,
+    },
+    /// The number of vertices is too large to allocate the graph.
+    TooManyVertices(usize),
+    /// An edge-list line does not consist of exactly two vertices.
+    InvalidEdge(String),
+    /// There is more input after the announced number of edges, or after the end of the document.
+    TrailingInput(String),
+    /// The JSON or GraphML document is malformed.
+    Syntax(String),
+    /// A required field or attribute is absent.
+    Missing(&'static str),
+    /// An edge refers to a node that is not declared.
+    UnknownVertex(String),
+    DuplicateVertex(String),
+    /// An edge weight is not a number.
+    InvalidWeight(String),
+    /// The document describes a directed graph where an undirected one is expected, or the other way around.
+    DirectionMismatch {
+        directed: bool,
+    },
+}
+
+/// Error returned when reading a graph, located by 1-based line number.
+#[derive(Debug)]
+pub struct ParseGraphError {
+    line: usize,
+    kind: ParseGraphErrorKind,
+}
+
+impl ParseGraphError {
+    fn new(line: usize, kind: ParseGraphErrorKind) -> ParseGraphError {
+        ParseGraphError { line: line, kind: kind }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn kind(&self) -> &ParseGraphErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            ParseGraphErrorKind::Io(ref err) => write!(f, "{}", err),
+            ParseGraphErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseGraphErrorKind::InvalidNumber(ref token) => write!(f, "invalid number {:?}", token),
+            ParseGraphErrorKind::VertexOutOfRange { vertex, vertices } => {
+                write!(f, "vertex {} is not between 0 and {}", vertex, vertices as isize - 1)
+            }
+            ParseGraphErrorKind::TooManyVertices(vertices) => write!(f, "cannot allocate {} vertices", vertices),
+            ParseGraphErrorKind::InvalidEdge(ref line) => write!(f, "invalid edge {:?}", line),
+            ParseGraphErrorKind::TrailingInput(ref token) => {
+                write!(f, "unexpected {:?} after the end of the graph", token)
+            }
+            ParseGraphErrorKind::Syntax(ref message) => write!(f, "{}", message),
+            ParseGraphErrorKind::Missing(what) => write!(f, "missing {}", what),
+            ParseGraphErrorKind::UnknownVertex(ref id) => write!(f, "unknown vertex {:?}", id),
+            ParseGraphErrorKind::DuplicateVertex(ref id) => write!(f, "duplicate vertex {:?}", id),
+            ParseGraphErrorKind::InvalidWeight(ref token) => write!(f, "invalid weight {:?}", token),
+            ParseGraphErrorKind::DirectionMismatch { directed: true } => write!(f, "expected an undirected graph"),
+            ParseGraphErrorKind::DirectionMismatch { directed: false } => write!(f, "expected a directed graph"),
+        }
+    }
+}
+
+impl error::Error for ParseGraphError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self.kind {
+            ParseGraphErrorKind::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Whitespace separated tokens, along with their line numbers.
+struct Tokens<R> {
+    lines: io::Lines<R>,
+    line: usize,
+    tokens: Vec<String>,
+}
+
+impl<R: BufRead> Tokens<R> {
+    fn new(reader: R) -> Tokens<R> {
+        Tokens {
+            lines: reader.lines(),
+            line: 0,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<String>, ParseGraphError> {
+        while self.tokens.is_empty() {
+            match self.lines.next() {
+                None => return Ok(None),
+                Some(line) => {
+                    self.line += 1;
+                    let line = line.map_err(|e| ParseGraphError::new(self.line, ParseGraphErrorKind::Io(e)))?;
+                    self.tokens = line.split_whitespace().rev().map(|t| t.to_owned()).collect();
+                }
+            }
+        }
+        Ok(self.tokens.pop())
+    }
+
+    fn next_number(&mut self) -> Result<usize, ParseGraphError> {
+        match self.next()? {
+            None => Err(ParseGraphError::new(self.line, ParseGraphErrorKind::UnexpectedEof)),
+            Some(token) => parse_number(self.line, &token),
+        }
+    }
+
+    fn next_vertex(&mut self, vertices: usize) -> Result<usize, ParseGraphError> {
+        let v = self.next_number()?;
+        check_vertex(self.line, v, vertices)
+    }
+}
+
+fn parse_number(line: usize, token: &str) -> Result<usize, ParseGraphError> {
+    token
+        .parse()
+        .map_err(|_| ParseGraphError::new(line, ParseGraphErrorKind::InvalidNumber(token.to_owned())))
+}
+
+fn parse_weight(line: usize, token: &str) -> Result<f64, ParseGraphError> {
+    match token.trim().parse::<f64>() {
+        Ok(weight) if !weight.is_nan() => Ok(weight),
+        _ => Err(ParseGraphError::new(line, ParseGraphErrorKind::InvalidWeight(token.to_owned()))),
+    }
+}
+
+fn check_vertex(line: usize, v: usize, vertices: usize) -> Result<usize, ParseGraphError> {
+    if v < vertices {
+        Ok(v)
+    } else {
+        Err(ParseGraphError::new(
+            line,
+            ParseGraphErrorKind::VertexOutOfRange {
+                vertex: v,
+                vertices: vertices,
+            },
+        ))
+    }
+}
+
+/// The number of vertices comes from the input, so check that the adjacency lists can be allocated before a graph
+/// tries to and aborts.
+fn check_vertices(line: usize, vertices: usize) -> Result<usize, ParseGraphError> {
+    match Vec::<Bag<usize>>::new().try_reserve_exact(vertices) {
+        Ok(()) => Ok(vertices),
+        Err(_) => Err(ParseGraphError::new(line, ParseGraphErrorKind::TooManyVertices(vertices))),
+    }
+}
+
+/// The textbook format: the number of vertices, the number of edges, then pairs of vertices.
+fn read_algs4<R: BufRead>(reader: R) -> Result<(usize, Vec<(usize, usize)>), ParseGraphError> {
+    let mut tokens = Tokens::new(reader);
+    let vertices = tokens.next_number()?;
+    let vertices = check_vertices(tokens.line, vertices)?;
+    let e = tokens.next_number()?;
+    // the edge count is not trusted to size an allocation, the edges themselves have to be there
+    let mut edges = Vec::new();
+    for _ in 0..e {
+        let v = tokens.next_vertex(vertices)?;
+        let w = tokens.next_vertex(vertices)?;
+        edges.push((v, w));
+    }
+    if let Some(token) = tokens.next()? {
+        return Err(ParseGraphError::new(tokens.line, ParseGraphErrorKind::TrailingInput(token)));
+    }
+    Ok((vertices, edges))
+}
+
+/// One edge per line, endpoints separated by whitespace or a comma. Blank lines and lines starting with `#` are
+/// skipped. The number of vertices is one more than the largest endpoint.
+fn read_edge_list<R: BufRead>(reader: R) -> Result<(usize, Vec<(usize, usize)>), ParseGraphError> {
+    let mut vertices = 0;
+    // line of the largest endpoint, which sets the number of vertices
+    let mut largest = 0;
+    let mut edges = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| ParseGraphError::new(i + 1, ParseGraphErrorKind::Io(e)))?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let fields = trimmed
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|f| !f.is_empty())
+            .collect::<Vec<&str>>();
+        if fields.len() != 2 {
+            return Err(ParseGraphError::new(i + 1, ParseGraphErrorKind::InvalidEdge(line.clone())));
+        }
+        let v = parse_number(i + 1, fields[0])?;
+        let w = parse_number(i + 1, fields[1])?;
+        // the largest endpoint plus one must still be a vertex count
+        let n = v.max(w).checked_add(1).ok_or_else(|| {
+            ParseGraphError::new(
+                i + 1,
+                ParseGraphErrorKind::VertexOutOfRange {
+                    vertex: v.max(w),
+                    vertices: usize::MAX,
+                },
+            )
+        })?;
+        if n > vertices {
+            vertices = n;
+            largest = i + 1;
+        }
+        edges.push((v, w));
+    }
+    Ok((check_vertices(largest, vertices)?, edges))
+}
+
+/// The vertices and edges of any of the graph types, as written to and read from JSON and GraphML.
+struct EdgeList {
+    directed: bool,
+    vertices: usize,
+    edges: Vec<(usize, usize, Option<f64>)>,
+}
+
+impl EdgeList {
+    fn of_graph(g: &Graph) -> EdgeList {
+        EdgeList {
+            directed: false,
+            vertices: g.vertices(),
+            edges: g.all_edges().into_iter().map(|(v, w)| (v, w, None)).collect(),
+        }
+    }
+
+    fn of_digraph(g: &Digraph) -> EdgeList {
+        EdgeList {
+            directed: true,
+            vertices: g.v(),
+            edges: g.all_edges().into_iter().map(|(v, w)| (v, w, None)).collect(),
+        }
+    }
+
+    fn of_edge_weighted_graph(g: &EdgeWeightedGraph) -> EdgeList {
+        EdgeList {
+            directed: false,
+            vertices: g.vertices(),
+            edges: g
+                .all_edges()
+                .into_iter()
+                .map(|e| (e.either(), e.other(e.either()), Some(e.weight())))
+                .collect(),
+        }
+    }
+
+    fn of_edge_weighted_digraph(g: &EdgeWeightedDigraph) -> EdgeList {
+        EdgeList {
+            directed: true,
+            vertices: g.v(),
+            edges: g
+                .all_edges()
+                .into_iter()
+                .map(|e| (e.from(), e.to(), Some(e.weight())))
+                .collect(),
+        }
+    }
+
+    fn is_weighted(&self) -> bool {
+        self.edges.iter().any(|e| e.2.is_some())
+    }
+
+    fn into_graph(self) -> Graph {
+        let mut g = Graph::new(self.vertices);
+        for (v, w, _) in self.edges {
+            g.add_edge(v, w);
+        }
+        g
+    }
+
+    fn into_digraph(self) -> Digraph {
+        let mut g = Digraph::new(self.vertices);
+        for (v, w, _) in self.edges {
+            g.add_edge(v, w);
+        }
+        g
+    }
+
+    // weights are checked to be present by `Document::resolve`
+    fn into_edge_weighted_graph(self) -> EdgeWeightedGraph {
+        let mut g = EdgeWeightedGraph::new(self.vertices);
+        for (v, w, weight) in self.edges {
+            g.add_edge(Edge::new(v, w, weight.unwrap()));
+        }
+        g
+    }
+
+    fn into_edge_weighted_digraph(self) -> EdgeWeightedDigraph {
+        let mut g = EdgeWeightedDigraph::new(self.vertices);
+        for (v, w, weight) in self.edges {
+            g.add_edge(DirectedEdge::new(v, w, weight.unwrap()));
+        }
+        g
+    }
+}
+
+/// A JSON or GraphML graph as read, before its node ids are resolved to vertices.
+struct Document {
+    /// Whether the graph is directed, and where that is said.
+    directed: Option<(usize, bool)>,
+    /// Node ids, with their line numbers.
+    nodes: Vec<(usize, String)>,
+    edges: Vec<DocumentEdge>,
+}
+
+struct DocumentEdge {
+    line: usize,
+    source: String,
+    target: String,
+    weight: Option<String>,
+}
+
+impl Document {
+    fn new() -> Document {
+        Document {
+            directed: None,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Number the nodes in the order they are declared.
+    fn resolve(self, directed: bool, weighted: bool) -> Result<EdgeList, ParseGraphError> {
+        if let Some((line, d)) = self.directed {
+            if d != directed {
+                return Err(ParseGraphError::new(line, ParseGraphErrorKind::DirectionMismatch { directed: d }));
+            }
+        }
+        let vertices = self.nodes.len();
+        let mut index = HashST::new();
+        for (v, (line, id)) in self.nodes.into_iter().enumerate() {
+            if index.get(&id).is_some() {
+                return Err(ParseGraphError::new(line, ParseGraphErrorKind::DuplicateVertex(id)));
+            }
+            index.put(id, v);
+        }
+        let vertex = |line: usize, id: &String| {
+            index
+                .get(id)
+                .cloned()
+                .ok_or_else(|| ParseGraphError::new(line, ParseGraphErrorKind::UnknownVertex(id.clone())))
+        };
+        let mut edges = Vec::with_capacity(self.edges.len());
+        for e in &self.edges {
+            let v = vertex(e.line, &e.source)?;
+            let w = vertex(e.line, &e.target)?;
+            let weight = match e.weight {
+                Some(ref token) => Some(parse_weight(e.line, token)?),
+                None if weighted => return Err(ParseGraphError::new(e.line, ParseGraphErrorKind::Missing("weight"))),
+                None => None,
+            };
+            edges.push((v, w, weight));
+        }
+        Ok(EdgeList {
+            directed: directed,
+            vertices: vertices,
+            edges: edges,
+        })
+    }
+}
+
+impl Graph {
+    /// Read a graph in the textbook format: the number of vertices, the number of edges, then one pair of
+    /// vertices per edge.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Graph, ParseGraphError> {
+        let (vertices, edges) = read_algs4(reader)?;
+        let mut g = Graph::new(vertices);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+        }
+        Ok(g)
+    }
+
+    /// Read a graph from a list of edges, one `v w` or `v,w` pair per line.
+    pub fn from_edge_list<R: BufRead>(reader: R) -> Result<Graph, ParseGraphError> {
+        let (vertices, edges) = read_edge_list(reader)?;
+        let mut g = Graph::new(vertices);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+        }
+        Ok(g)
+    }
+}
+
+impl FromStr for Graph {
+    type Err = ParseGraphError;
+
+    fn from_str(s: &str) -> Result<Graph, ParseGraphError> {
+        Graph::from_reader(s.as_bytes())
+    }
+}
+
+impl Digraph {
+    /// Read a digraph in the textbook format: the number of vertices, the number of edges, then one pair of
+    /// vertices per edge.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Digraph, ParseGraphError> {
+        let (vertices, edges) = read_algs4(reader)?;
+        let mut g = Digraph::new(vertices);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+        }
+        Ok(g)
+    }
+
+    /// Read a digraph from a list of edges, one `v w` or `v,w` pair per line.
+    pub fn from_edge_list<R: BufRead>(reader: R) -> Result<Digraph, ParseGraphError> {
+        let (vertices, edges) = read_edge_list(reader)?;
+        let mut g = Digraph::new(vertices);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+        }
+        Ok(g)
+    }
+}
+
+impl FromStr for Digraph {
+    type Err = ParseGraphError;
+
+    fn from_str(s: &str) -> Result<Digraph, ParseGraphError> {
+        Digraph::from_reader(s.as_bytes())
+    }
+}
+
+#[test]
+fn test_parse_graph() {
+    let g: Graph = "13\n13\n0 5\n4 3\n0 1\n9 12\n6 4\n5 4\n0 2\n11 12\n9 10\n0 6\n7 8\n9 11\n5 3\n"
+        .parse()
+        .unwrap();
+    assert_eq!(13, g.vertices());
+    assert_eq!(13, g.edges());
+    assert_eq!(4, g.degree(0));
+    assert_eq!(3, g.connected_components().count());
+
+    // tokens need not be one edge per line
+    let g: Digraph = "4 3\n0 1 1 2\n\n2 3".parse().unwrap();
+    assert_eq!(4, g.v());
+    assert_eq!(3, g.e());
+    assert_eq!(vec![1], g.adj(0));
+}
+
+#[test]
+fn test_parse_graph_errors() {
+    let err = "3\n2\n0 1\n1 x\n".parse::<Graph>().err().unwrap();
+    assert_eq!(4, err.line());
+    assert_eq!("line 4: invalid number \"x\"", err.to_string());
+
+    let err = "3\n2\n0 1\n1 3\n".parse::<Digraph>().err().unwrap();
+    assert_eq!(4, err.line());
+    match *err.kind() {
+        ParseGraphErrorKind::VertexOutOfRange { vertex, vertices } => assert_eq!((3, 3), (vertex, vertices)),
+        ref kind => panic!("unexpected {:?}", kind),
+    }
+
+    let err = "3\n2\n0 1\n".parse::<Graph>().err().unwrap();
+    assert_eq!(3, err.line());
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::UnexpectedEof => true,
+        _ => false,
+    });
+
+    let err = "3\n1\n0 1\n2 0\n".parse::<Graph>().err().unwrap();
+    assert_eq!(4, err.line());
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::TrailingInput(ref token) => token == "2",
+        _ => false,
+    });
+
+    assert_eq!(1, "-1\n0\n".parse::<Graph>().err().unwrap().line());
+
+    // an edge count far beyond the input runs out of edges rather than memory
+    let err = "2 100000000000000000".parse::<Digraph>().err().unwrap();
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::UnexpectedEof => true,
+        _ => false,
+    });
+    let err = "1 18446744073709551615".parse::<Graph>().err().unwrap();
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::UnexpectedEof => true,
+        _ => false,
+    });
+
+    // and so does a vertex count beyond any memory
+    let err = "18446744073709551615 0".parse::<Graph>().err().unwrap();
+    assert_eq!("line 1: cannot allocate 18446744073709551615 vertices", err.to_string());
+}
+
+#[test]
+fn test_parse_edge_list() {
+    let input = "# dependencies\n0,1\n1, 2\n\n3 1\n";
+    let g = Digraph::from_edge_list(input.as_bytes()).unwrap();
+    assert_eq!(4, g.v());
+    assert_eq!(3, g.e());
+    assert_eq!(vec![1], g.adj(3));
+
+    let g = Graph::from_edge_list(input.as_bytes()).unwrap();
+    assert_eq!(2, g.degree(2) + g.degree(3));
+
+    let err = Graph::from_edge_list("0 1\n1 2 3\n".as_bytes()).err().unwrap();
+    assert_eq!(2, err.line());
+    assert_eq!("line 2: invalid edge \"1 2 3\"", err.to_string());
+
+    let err = Digraph::from_edge_list("0 1\n0 18446744073709551615\n".as_bytes())
+        .err()
+        .unwrap();
+    assert_eq!(2, err.line());
+    match *err.kind() {
+        ParseGraphErrorKind::VertexOutOfRange { vertex, .. } => assert_eq!(usize::MAX, vertex),
+        ref kind => panic!("unexpected {:?}", kind),
+    }
+
+    let err = Digraph::from_edge_list("0 1\n0 100000000000000\n1 2\n".as_bytes())
+        .err()
+        .unwrap();
+    assert_eq!("line 2: cannot allocate 100000000000001 vertices", err.to_string());
+}
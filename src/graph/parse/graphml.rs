@@ -0,0 +1,495 @@
+//! GraphML, as written by and read into most graph tools. Edge weights are kept in the `data` of a `key` whose
+//! `attr.name` is `weight`. Vertices are numbered in the order the nodes are declared.
+
+use super::super::{Digraph, EdgeWeightedDigraph, EdgeWeightedGraph, Graph};
+#[cfg(test)]
+use super::super::{DirectedEdge, Edge};
+use super::{Document, DocumentEdge, EdgeList, ParseGraphError, ParseGraphErrorKind};
+
+fn write(g: &EdgeList) -> String {
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    if g.is_weighted() {
+        xml.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+    }
+    xml.push_str(&format!(
+        "  <graph id=\"G\" edgedefault=\"{}\">\n",
+        if g.directed { "directed" } else { "undirected" }
+    ));
+    for v in 0..g.vertices {
+        xml.push_str(&format!("    <node id=\"n{}\"/>\n", v));
+    }
+    for &(v, w, weight) in &g.edges {
+        match weight {
+            Some(weight) => xml.push_str(&format!(
+                "    <edge source=\"n{}\" target=\"n{}\">\n      <data key=\"weight\">{}</data>\n    </edge>\n",
+                v, w, weight
+            )),
+            None => xml.push_str(&format!("    <edge source=\"n{}\" target=\"n{}\"/>\n", v, w)),
+        }
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+enum Event {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Just enough of an XML tokenizer for GraphML: no DTDs, and entities limited to the predefined and numeric ones.
+struct Xml<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Xml<'a> {
+    fn new(input: &'a str) -> Xml<'a> {
+        Xml {
+            input: input,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn error(&self, kind: ParseGraphErrorKind) -> ParseGraphError {
+        ParseGraphError::new(self.line, kind)
+    }
+
+    fn syntax_error(&self, message: &str) -> ParseGraphError {
+        self.error(ParseGraphErrorKind::Syntax(message.to_owned()))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.line += self.input[self.pos..self.pos + n].matches('\n').count();
+        self.pos += n;
+    }
+
+    /// Everything up to `end`, which is skipped as well.
+    fn take_until(&mut self, end: &str) -> Result<&'a str, ParseGraphError> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let taken = &self.rest()[..i];
+                self.advance(i + end.len());
+                Ok(taken)
+            }
+            None => {
+                let n = self.rest().len();
+                self.advance(n);
+                Err(self.error(ParseGraphErrorKind::UnexpectedEof))
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let n = self.rest().len() - self.rest().trim_start().len();
+        self.advance(n);
+    }
+
+    fn take_name(&mut self) -> Result<&'a str, ParseGraphError> {
+        let n = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/' || c == '>')
+            .unwrap_or_else(|| self.rest().len());
+        if n == 0 {
+            return Err(self.syntax_error("expected a name"));
+        }
+        let name = &self.rest()[..n];
+        self.advance(n);
+        Ok(name)
+    }
+
+    fn next(&mut self) -> Result<Option<Event>, ParseGraphError> {
+        loop {
+            if self.rest().is_empty() {
+                return Ok(None);
+            } else if !self.rest().starts_with('<') {
+                let n = self.rest().find('<').unwrap_or_else(|| self.rest().len());
+                let text = &self.rest()[..n];
+                let text = self.unescape(text)?;
+                self.advance(n);
+                return Ok(Some(Event::Text(text)));
+            } else if self.rest().starts_with("<?") {
+                self.take_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.advance("<![CDATA[".len());
+                return Ok(Some(Event::Text(self.take_until("]]>")?.to_owned())));
+            } else if self.rest().starts_with("<!") {
+                self.take_until(">")?;
+            } else if self.rest().starts_with("</") {
+                self.advance(2);
+                let name = local_name(self.take_until(">")?.trim_end());
+                return Ok(Some(Event::End(name)));
+            } else {
+                self.advance(1);
+                return self.start_tag().map(Some);
+            }
+        }
+    }
+
+    // after the `<`
+    fn start_tag(&mut self) -> Result<Event, ParseGraphError> {
+        let name = local_name(self.take_name()?);
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.advance(2);
+                return Ok(Event::Start {
+                    name: name,
+                    attributes: attributes,
+                    empty: true,
+                });
+            } else if self.rest().starts_with('>') {
+                self.advance(1);
+                return Ok(Event::Start {
+                    name: name,
+                    attributes: attributes,
+                    empty: false,
+                });
+            } else if self.rest().is_empty() {
+                return Err(self.error(ParseGraphErrorKind::UnexpectedEof));
+            }
+            let attribute = self.take_name()?.to_owned();
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.syntax_error("expected '='"));
+            }
+            self.advance(1);
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return Err(self.syntax_error("expected a quoted attribute value")),
+            };
+            self.advance(1);
+            let value = self.take_until(if quote == '"' { "\"" } else { "'" })?;
+            attributes.push((attribute, self.unescape(value)?));
+        }
+    }
+
+    fn unescape(&self, s: &str) -> Result<String, ParseGraphError> {
+        let mut unescaped = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(i) = rest.find('&') {
+            unescaped.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+            let end = rest.find(';').ok_or_else(|| self.syntax_error("unterminated entity"))?;
+            let entity = &rest[..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                    .ok()
+                    .and_then(::std::char::from_u32),
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(::std::char::from_u32),
+                _ => None,
+            };
+            unescaped.push(c.ok_or_else(|| self.syntax_error(&format!("unknown entity &{};", entity)))?);
+            rest = &rest[end + 1..];
+        }
+        unescaped.push_str(rest);
+        Ok(unescaped)
+    }
+}
+
+/// The element name without its namespace prefix.
+fn local_name(name: &str) -> String {
+    name.rsplit(':').next().unwrap().to_owned()
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|a| a.0 == name).map(|a| &a.1[..])
+}
+
+/// The keys of edge weights, along with their default values.
+struct WeightKey {
+    id: String,
+    default: Option<String>,
+}
+
+fn read(input: &str) -> Result<Document, ParseGraphError> {
+    let mut xml = Xml::new(input);
+    let mut doc = Document::new();
+    let mut open: Vec<String> = Vec::new();
+    let mut graphs = 0;
+    let mut weight_keys: Vec<WeightKey> = Vec::new();
+    // the weight key, edge and weight text being read
+    let mut key: Option<usize> = None;
+    let mut edge: Option<DocumentEdge> = None;
+    let mut text: Option<String> = None;
+
+    while let Some(event) = xml.next()? {
+        match event {
+            Event::Start {
+                name,
+                attributes,
+                empty,
+            } => {
+                let required = |name: &'static str| {
+                    attribute(&attributes, name)
+                        .map(|value| value.to_owned())
+                        .ok_or_else(|| xml.error(ParseGraphErrorKind::Missing(name)))
+                };
+                let in_graph = open.last().map(String::as_str) == Some("graph");
+                match &name[..] {
+                    "key" => {
+                        let domain = attribute(&attributes, "for").unwrap_or("all");
+                        if attribute(&attributes, "attr.name") == Some("weight") &&
+                            (domain == "edge" || domain == "all")
+                        {
+                            weight_keys.push(WeightKey {
+                                id: required("id")?,
+                                default: None,
+                            });
+                            key = Some(weight_keys.len() - 1);
+                        }
+                    }
+                    "default" if key.is_some() => text = Some(String::new()),
+                    "graph" => {
+                        graphs += 1;
+                        if graphs > 1 {
+                            return Err(xml.syntax_error("more than one graph"));
+                        }
+                        doc.directed = match attribute(&attributes, "edgedefault") {
+                            None => None,
+                            Some("directed") => Some((xml.line, true)),
+                            Some("undirected") => Some((xml.line, false)),
+                            Some(other) => return Err(xml.syntax_error(&format!("invalid edgedefault {:?}", other))),
+                        };
+                    }
+                    "node" if in_graph => doc.nodes.push((xml.line, required("id")?)),
+                    "edge" if in_graph => {
+                        edge = Some(DocumentEdge {
+                            line: xml.line,
+                            source: required("source")?,
+                            target: required("target")?,
+                            weight: None,
+                        })
+                    }
+                    "node" | "edge" => return Err(xml.syntax_error(&format!("<{}> outside of <graph>", name))),
+                    "data" if edge.is_some() => {
+                        if let Some(k) = attribute(&attributes, "key") {
+                            if weight_keys.iter().any(|w| w.id == k) {
+                                text = Some(String::new());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                if empty {
+                    // an empty element ends right away
+                    end_element(&name, &mut doc, &mut weight_keys, &mut key, &mut edge, &mut text);
+                } else {
+                    open.push(name);
+                }
+            }
+            Event::End(name) => {
+                match open.pop() {
+                    Some(ref expected) if *expected == name => {}
+                    Some(expected) => return Err(xml.syntax_error(&format!("expected </{}>", expected))),
+                    None => return Err(xml.syntax_error(&format!("unexpected </{}>", name))),
+                }
+                end_element(&name, &mut doc, &mut weight_keys, &mut key, &mut edge, &mut text);
+            }
+            Event::Text(t) => {
+                if let Some(ref mut text) = text {
+                    text.push_str(&t);
+                }
+            }
+        }
+    }
+    if !open.is_empty() {
+        return Err(xml.error(ParseGraphErrorKind::UnexpectedEof));
+    }
+    if graphs == 0 {
+        return Err(xml.error(ParseGraphErrorKind::Missing("graph")));
+    }
+    Ok(doc)
+}
+
+fn end_element(
+    name: &str,
+    doc: &mut Document,
+    weight_keys: &mut [WeightKey],
+    key: &mut Option<usize>,
+    edge: &mut Option<DocumentEdge>,
+    text: &mut Option<String>,
+) {
+    match name {
+        "key" => *key = None,
+        "default" => {
+            if let Some(k) = *key {
+                weight_keys[k].default = text.take();
+            }
+        }
+        "data" => {
+            if let Some(ref mut edge) = *edge {
+                if text.is_some() {
+                    edge.weight = text.take();
+                }
+            }
+        }
+        "edge" => {
+            if let Some(mut edge) = edge.take() {
+                if edge.weight.is_none() {
+                    edge.weight = weight_keys.iter().filter_map(|k| k.default.clone()).next();
+                }
+                doc.edges.push(edge);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Graph {
+    pub fn to_graphml(&self) -> String {
+        write(&EdgeList::of_graph(self))
+    }
+
+    pub fn from_graphml(s: &str) -> Result<Graph, ParseGraphError> {
+        Ok(read(s)?.resolve(false, false)?.into_graph())
+    }
+}
+
+impl Digraph {
+    pub fn to_graphml(&self) -> String {
+        write(&EdgeList::of_digraph(self))
+    }
+
+    pub fn from_graphml(s: &str) -> Result<Digraph, ParseGraphError> {
+        Ok(read(s)?.resolve(true, false)?.into_digraph())
+    }
+}
+
+impl EdgeWeightedGraph {
+    pub fn to_graphml(&self) -> String {
+        write(&EdgeList::of_edge_weighted_graph(self))
+    }
+
+    pub fn from_graphml(s: &str) -> Result<EdgeWeightedGraph, ParseGraphError> {
+        Ok(read(s)?.resolve(false, true)?.into_edge_weighted_graph())
+    }
+}
+
+impl EdgeWeightedDigraph {
+    pub fn to_graphml(&self) -> String {
+        write(&EdgeList::of_edge_weighted_digraph(self))
+    }
+
+    pub fn from_graphml(s: &str) -> Result<EdgeWeightedDigraph, ParseGraphError> {
+        Ok(read(s)?.resolve(true, true)?.into_edge_weighted_digraph())
+    }
+}
+
+#[cfg(test)]
+fn sorted<T: PartialOrd>(mut edges: Vec<T>) -> Vec<T> {
+    edges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    edges
+}
+
+#[test]
+fn test_graphml_round_trip() {
+    let g: Graph = "5 6\n0 1\n1 2\n2 0\n3 3\n1 2\n0 4".parse().unwrap();
+    let h = Graph::from_graphml(&g.to_graphml()).unwrap();
+    assert_eq!(5, h.vertices());
+    assert_eq!(sorted(g.all_edges()), sorted(h.all_edges()));
+    assert!(!g.to_graphml().contains("<key"));
+
+    let g: Digraph = "4 4\n0 1\n1 0\n2 2\n3 1".parse().unwrap();
+    let graphml = g.to_graphml();
+    assert!(graphml.contains("<graph id=\"G\" edgedefault=\"directed\">\n    <node id=\"n0\"/>\n"));
+    let h = Digraph::from_graphml(&graphml).unwrap();
+    assert_eq!(g.adjacency_matrix(), h.adjacency_matrix());
+
+    let mut g = EdgeWeightedGraph::new(3);
+    g.add_edge(Edge::new(0, 1, 0.1));
+    g.add_edge(Edge::new(1, 2, -2.5e-8));
+    g.add_edge(Edge::new(2, 2, 1e300));
+    let h = EdgeWeightedGraph::from_graphml(&g.to_graphml()).unwrap();
+    let weights = |g: &EdgeWeightedGraph| sorted(g.all_edges().iter().map(|e| e.weight()).collect());
+    assert_eq!(weights(&g), weights(&h));
+
+    let mut g = EdgeWeightedDigraph::new(2);
+    g.add_edge(DirectedEdge::new(0, 1, 0.25));
+    g.add_edge(DirectedEdge::new(1, 0, 3.0));
+    let h = EdgeWeightedDigraph::from_graphml(&g.to_graphml()).unwrap();
+    let edges =
+        |g: &EdgeWeightedDigraph| sorted(g.all_edges().iter().map(|e| (e.from(), e.to(), e.weight())).collect());
+    assert_eq!(edges(&g), edges(&h));
+
+    // weights are dropped when read as an unweighted graph
+    assert_eq!(2, Digraph::from_graphml(&g.to_graphml()).unwrap().e());
+}
+
+#[test]
+fn test_graphml_foreign() {
+    let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- written by hand -->
+<g:graphml xmlns:g="http://graphml.graphdrawing.org/xmlns">
+  <g:key id="d0" for="node" attr.name="weight" attr.type="double"/>
+  <g:key id="d1" for="edge" attr.name="weight" attr.type="double">
+    <g:default>1.5</g:default>
+  </g:key>
+  <g:graph id="routes" edgedefault='undirected'>
+    <g:node id="A &amp; B"><g:data key="d0">9</g:data></g:node>
+    <g:node id="C"/>
+    <g:edge source="A &amp; B" target="C"><g:data key="d1"><![CDATA[ 2.5 ]]></g:data></g:edge>
+    <g:edge id="e1" source="C" target="C"/>
+  </g:graph>
+</g:graphml>
+"#;
+    let g = EdgeWeightedGraph::from_graphml(input).unwrap();
+    assert_eq!(2, g.vertices());
+    assert_eq!(vec![2.5, 1.5], g.all_edges().iter().map(|e| e.weight()).collect::<Vec<f64>>());
+    assert_eq!(vec![(0, 1), (1, 1)], Graph::from_graphml(input).unwrap().all_edges());
+}
+
+#[test]
+fn test_graphml_errors() {
+    let graph = |body: &str| {
+        format!("<graphml>\n<graph edgedefault=\"directed\">\n<node id=\"a\"/>\n{}\n</graph>\n</graphml>", body)
+    };
+    let err = Graph::from_graphml(&graph("")).err().unwrap();
+    assert_eq!("line 2: expected an undirected graph", err.to_string());
+
+    let err = Digraph::from_graphml(&graph("<edge source=\"a\" target=\"b\"/>"))
+        .err()
+        .unwrap();
+    assert_eq!("line 4: unknown vertex \"b\"", err.to_string());
+
+    let err = Digraph::from_graphml(&graph("<edge source=\"a\"/>")).err().unwrap();
+    assert_eq!("line 4: missing target", err.to_string());
+
+    let err = EdgeWeightedDigraph::from_graphml(&graph("<edge source=\"a\" target=\"a\"/>"))
+        .err()
+        .unwrap();
+    assert_eq!("line 4: missing weight", err.to_string());
+
+    let err = Digraph::from_graphml(&graph("<node id=\"b\">")).err().unwrap();
+    assert_eq!("line 5: expected </node>", err.to_string());
+
+    let err = Digraph::from_graphml("<graphml>\n<graph>\n").err().unwrap();
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::UnexpectedEof => true,
+        _ => false,
+    });
+    assert!(Digraph::from_graphml("<graphml/>").is_err());
+    assert!(Digraph::from_graphml(&graph("<node id=\"&bogus;\"/>")).is_err());
+}
@@ -0,0 +1,538 @@
+//! A node-link JSON format:
+//!
+//! ```json
+//! {
+//!   "directed": true,
+//!   "nodes": [
+//!     {"id": 0},
+//!     {"id": 1}
+//!   ],
+//!   "edges": [
+//!     {"source": 0, "target": 1, "weight": 0.5}
+//!   ]
+//! }
+//! ```
+//!
+//! Node ids may also be strings, and `links` is accepted in place of `edges`. Vertices are numbered in the order
+//! the nodes are listed. JSON has no infinities or NaN, so infinite weights are written as the strings `"inf"`
+//! and `"-inf"`, and read back from them.
+
+use super::super::{Digraph, EdgeWeightedDigraph, EdgeWeightedGraph, Graph};
+#[cfg(test)]
+use super::super::{DirectedEdge, Edge};
+use super::{Document, DocumentEdge, EdgeList, ParseGraphError, ParseGraphErrorKind};
+
+fn write(g: &EdgeList) -> String {
+    let mut json = String::new();
+
+    json.push_str(&format!("{{\n  \"directed\": {},\n  \"nodes\": [", g.directed));
+    for v in 0..g.vertices {
+        json.push_str(if v == 0 { "\n" } else { ",\n" });
+        json.push_str(&format!("    {{\"id\": {}}}", v));
+    }
+    json.push_str(if g.vertices == 0 { "],\n" } else { "\n  ],\n" });
+
+    json.push_str("  \"edges\": [");
+    for (i, &(v, w, weight)) in g.edges.iter().enumerate() {
+        json.push_str(if i == 0 { "\n" } else { ",\n" });
+        match weight {
+            Some(weight) if !weight.is_finite() => {
+                json.push_str(&format!("    {{\"source\": {}, \"target\": {}, \"weight\": \"{}\"}}", v, w, weight))
+            }
+            Some(weight) => {
+                json.push_str(&format!("    {{\"source\": {}, \"target\": {}, \"weight\": {}}}", v, w, weight))
+            }
+            None => json.push_str(&format!("    {{\"source\": {}, \"target\": {}}}", v, w)),
+        }
+    }
+    json.push_str(if g.edges.is_empty() { "]\n}\n" } else { "\n  ]\n}\n" });
+    json
+}
+
+enum Value {
+    Null,
+    Bool(bool),
+    /// The number as written, so that integer ids keep their exact spelling.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// A JSON value, along with the line where it starts.
+struct Json {
+    line: usize,
+    value: Value,
+}
+
+impl Json {
+    fn error(&self, kind: ParseGraphErrorKind) -> ParseGraphError {
+        ParseGraphError::new(self.line, kind)
+    }
+
+    fn field(&self, key: &str) -> Option<&Json> {
+        match self.value {
+            Value::Object(ref fields) => fields.iter().find(|f| f.0 == key).map(|f| &f.1),
+            _ => None,
+        }
+    }
+
+    fn required(&self, key: &'static str) -> Result<&Json, ParseGraphError> {
+        self.field(key)
+            .ok_or_else(|| self.error(ParseGraphErrorKind::Missing(key)))
+    }
+
+    fn as_array(&self, what: &str) -> Result<&[Json], ParseGraphError> {
+        match self.value {
+            Value::Array(ref items) => Ok(items),
+            _ => Err(self.error(ParseGraphErrorKind::Syntax(format!("{} is not an array", what)))),
+        }
+    }
+
+    fn as_id(&self) -> Result<String, ParseGraphError> {
+        match self.value {
+            Value::Number(ref n) => Ok(n.clone()),
+            Value::String(ref s) => Ok(s.clone()),
+            _ => Err(self.error(ParseGraphErrorKind::Syntax("node id is not a number or a string".to_owned()))),
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects accepted, which bounds the recursion of the parser.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Parser<'a> {
+        Parser {
+            input: input,
+            pos: 0,
+            line: 1,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, kind: ParseGraphErrorKind) -> ParseGraphError {
+        ParseGraphError::new(self.line, kind)
+    }
+
+    fn unexpected(&self) -> ParseGraphError {
+        match self.peek() {
+            None => self.error(ParseGraphErrorKind::UnexpectedEof),
+            Some(c) => self.error(ParseGraphErrorKind::Syntax(format!("unexpected character {:?}", c))),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseGraphError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn parse_document(&mut self) -> Result<Json, ParseGraphError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(value),
+            Some(c) => Err(self.error(ParseGraphErrorKind::TrailingInput(c.to_string()))),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, ParseGraphError> {
+        self.skip_whitespace();
+        let line = self.line;
+        let value = match self.peek() {
+            Some(c @ '{') | Some(c @ '[') => {
+                if self.depth == MAX_DEPTH {
+                    let message = format!("nested deeper than {} levels", MAX_DEPTH);
+                    return Err(self.error(ParseGraphErrorKind::Syntax(message)));
+                }
+                self.depth += 1;
+                let value = if c == '{' {
+                    self.parse_object()?
+                } else {
+                    self.parse_array()?
+                };
+                self.depth -= 1;
+                value
+            }
+            Some('"') => Value::String(self.parse_string()?),
+            Some('-') | Some('0'..='9') => self.parse_number()?,
+            Some('t') => self.parse_literal("true", Value::Bool(true))?,
+            Some('f') => self.parse_literal("false", Value::Bool(false))?,
+            Some('n') => self.parse_literal("null", Value::Null)?,
+            _ => return Err(self.unexpected()),
+        };
+        Ok(Json {
+            line: line,
+            value: value,
+        })
+    }
+
+    fn parse_object(&mut self) -> Result<Value, ParseGraphError> {
+        let mut fields = Vec::new();
+        self.expect('{')?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some('}') => return Ok(Value::Object(fields)),
+                None => return Err(self.error(ParseGraphErrorKind::UnexpectedEof)),
+                _ => return Err(self.error(ParseGraphErrorKind::Syntax("expected ',' or '}'".to_owned()))),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, ParseGraphError> {
+        let mut items = Vec::new();
+        self.expect('[')?;
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.bump();
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some(']') => return Ok(Value::Array(items)),
+                None => return Err(self.error(ParseGraphErrorKind::UnexpectedEof)),
+                _ => return Err(self.error(ParseGraphErrorKind::Syntax("expected ',' or ']'".to_owned()))),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseGraphError> {
+        let mut s = String::new();
+        self.bump(); // the opening quote
+        loop {
+            match self.bump() {
+                None => return Err(self.error(ParseGraphErrorKind::UnexpectedEof)),
+                Some('"') => return Ok(s),
+                Some('\\') => {
+                    let c = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error(ParseGraphErrorKind::Syntax("invalid escape".to_owned()))),
+                    };
+                    s.push(c);
+                }
+                Some(c) if c < ' ' => {
+                    return Err(self.error(ParseGraphErrorKind::Syntax("control character in string".to_owned())))
+                }
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, ParseGraphError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .and_then(|d| u32::from_str_radix(d, 16).ok());
+        match digits {
+            Some(n) => {
+                self.pos += 4;
+                Ok(n)
+            }
+            None => Err(self.error(ParseGraphErrorKind::Syntax("invalid unicode escape".to_owned()))),
+        }
+    }
+
+    // after the `\u`
+    fn parse_unicode_escape(&mut self) -> Result<char, ParseGraphError> {
+        let mut n = self.parse_hex4()?;
+        if (0xd800..0xdc00).contains(&n) && self.input[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.parse_hex4()?;
+            if (0xdc00..0xe000).contains(&low) {
+                n = 0x10000 + ((n - 0xd800) << 10) + (low - 0xdc00);
+            }
+        }
+        ::std::char::from_u32(n)
+            .ok_or_else(|| self.error(ParseGraphErrorKind::Syntax("invalid unicode escape".to_owned())))
+    }
+
+    fn parse_number(&mut self) -> Result<Value, ParseGraphError> {
+        let start = self.pos;
+        while let Some('-') | Some('+') | Some('.') | Some('e') | Some('E') | Some('0'..='9') = self.peek() {
+            self.bump();
+        }
+        let number = &self.input[start..self.pos];
+        match number.parse::<f64>() {
+            Ok(_) => Ok(Value::Number(number.to_owned())),
+            Err(_) => Err(self.error(ParseGraphErrorKind::InvalidNumber(number.to_owned()))),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Value) -> Result<Value, ParseGraphError> {
+        if self.input[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.unexpected())
+        }
+    }
+}
+
+fn read(input: &str) -> Result<Document, ParseGraphError> {
+    let json = Parser::new(input).parse_document()?;
+    match json.value {
+        Value::Object(_) => {}
+        _ => return Err(json.error(ParseGraphErrorKind::Syntax("expected an object".to_owned()))),
+    }
+
+    let mut doc = Document::new();
+    if let Some(directed) = json.field("directed") {
+        match directed.value {
+            Value::Bool(d) => doc.directed = Some((directed.line, d)),
+            _ => return Err(directed.error(ParseGraphErrorKind::Syntax("directed is not a boolean".to_owned()))),
+        }
+    }
+    for node in json.required("nodes")?.as_array("nodes")? {
+        doc.nodes.push((node.line, node.required("id")?.as_id()?));
+    }
+    let edges = match json.field("edges").or_else(|| json.field("links")) {
+        Some(edges) => edges,
+        None => return Err(json.error(ParseGraphErrorKind::Missing("edges"))),
+    };
+    for edge in edges.as_array("edges")? {
+        let weight = match edge.field("weight") {
+            None | Some(&Json { value: Value::Null, .. }) => None,
+            Some(&Json {
+                value: Value::Number(ref n),
+                ..
+            }) => Some(n.clone()),
+            Some(&Json {
+                value: Value::String(ref s),
+                ..
+            }) if s == "inf" || s == "-inf" || s == "NaN" => Some(s.clone()),
+            Some(_) => return Err(edge.error(ParseGraphErrorKind::InvalidWeight("not a number".to_owned()))),
+        };
+        doc.edges.push(DocumentEdge {
+            line: edge.line,
+            source: edge.required("source")?.as_id()?,
+            target: edge.required("target")?.as_id()?,
+            weight: weight,
+        });
+    }
+    Ok(doc)
+}
+
+impl Graph {
+    /// Write the graph as a JSON object of `nodes` and `edges`.
+    pub fn to_json(&self) -> String {
+        write(&EdgeList::of_graph(self))
+    }
+
+    pub fn from_json(s: &str) -> Result<Graph, ParseGraphError> {
+        Ok(read(s)?.resolve(false, false)?.into_graph())
+    }
+}
+
+impl Digraph {
+    /// Write the digraph as a JSON object of `nodes` and `edges`.
+    pub fn to_json(&self) -> String {
+        write(&EdgeList::of_digraph(self))
+    }
+
+    pub fn from_json(s: &str) -> Result<Digraph, ParseGraphError> {
+        Ok(read(s)?.resolve(true, false)?.into_digraph())
+    }
+}
+
+impl EdgeWeightedGraph {
+    /// Write the graph as a JSON object of `nodes` and `edges`, each edge with its `weight`.
+    pub fn to_json(&self) -> String {
+        write(&EdgeList::of_edge_weighted_graph(self))
+    }
+
+    pub fn from_json(s: &str) -> Result<EdgeWeightedGraph, ParseGraphError> {
+        Ok(read(s)?.resolve(false, true)?.into_edge_weighted_graph())
+    }
+}
+
+impl EdgeWeightedDigraph {
+    /// Write the digraph as a JSON object of `nodes` and `edges`, each edge with its `weight`.
+    pub fn to_json(&self) -> String {
+        write(&EdgeList::of_edge_weighted_digraph(self))
+    }
+
+    pub fn from_json(s: &str) -> Result<EdgeWeightedDigraph, ParseGraphError> {
+        Ok(read(s)?.resolve(true, true)?.into_edge_weighted_digraph())
+    }
+}
+
+#[cfg(test)]
+fn sorted<T: PartialOrd>(mut edges: Vec<T>) -> Vec<T> {
+    edges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    edges
+}
+
+#[test]
+fn test_json_round_trip() {
+    let g: Graph = "5 6\n0 1\n1 2\n2 0\n3 3\n1 2\n0 4".parse().unwrap();
+    let json = g.to_json();
+    assert!(json.starts_with("{\n  \"directed\": false,\n  \"nodes\": [\n    {\"id\": 0},"));
+    let h = Graph::from_json(&json).unwrap();
+    assert_eq!(5, h.vertices());
+    assert_eq!(sorted(g.all_edges()), sorted(h.all_edges()));
+    assert_eq!(g.adjacency_matrix(), h.adjacency_matrix());
+
+    let g: Digraph = "4 4\n0 1\n1 0\n2 2\n3 1".parse().unwrap();
+    let h = Digraph::from_json(&g.to_json()).unwrap();
+    assert_eq!(sorted(g.all_edges()), sorted(h.all_edges()));
+
+    let empty = Digraph::from_json(&Digraph::new(0).to_json()).unwrap();
+    assert_eq!((0, 0), (empty.v(), empty.e()));
+
+    let mut g = EdgeWeightedGraph::new(3);
+    g.add_edge(Edge::new(0, 1, 0.1));
+    g.add_edge(Edge::new(1, 2, -2.5e-8));
+    g.add_edge(Edge::new(2, 2, 1e300));
+    let h = EdgeWeightedGraph::from_json(&g.to_json()).unwrap();
+    let weights = |g: &EdgeWeightedGraph| sorted(g.all_edges().iter().map(|e| e.weight()).collect());
+    assert_eq!(weights(&g), weights(&h));
+    assert_eq!(g.kruskal_mst().weight(), h.kruskal_mst().weight());
+
+    let mut g = EdgeWeightedDigraph::new(2);
+    g.add_edge(DirectedEdge::new(0, 1, 0.25));
+    g.add_edge(DirectedEdge::new(1, 0, 3.0));
+    let h = EdgeWeightedDigraph::from_json(&g.to_json()).unwrap();
+    let edges =
+        |g: &EdgeWeightedDigraph| sorted(g.all_edges().iter().map(|e| (e.from(), e.to(), e.weight())).collect());
+    assert_eq!(edges(&g), edges(&h));
+}
+
+#[test]
+fn test_json_node_link() {
+    let input = r#"{"directed": true, "multigraph": false, "graph": {},
+        "nodes": [{"id": "a\u00e9"}, {"id": "b", "label": [1, null]}, {"id": 7}],
+        "links": [{"source": "a\u00e9", "target": "b"}, {"source": 7, "target": "b", "weight": 2}]}"#;
+    let g = Digraph::from_json(input).unwrap();
+    assert_eq!(3, g.v());
+    assert_eq!(vec![(0, 1), (2, 1)], g.all_edges());
+    let g = EdgeWeightedDigraph::from_json(&input.replace("\"b\"}", "\"b\", \"weight\": 1.5}")).unwrap();
+    assert_eq!(3.5, g.all_edges().iter().map(|e| e.weight()).sum::<f64>());
+}
+
+#[test]
+fn test_json_errors() {
+    let err = Graph::from_json("{\"directed\": true, \"nodes\": [], \"edges\": []}")
+        .err()
+        .unwrap();
+    assert_eq!("line 1: expected an undirected graph", err.to_string());
+
+    let err = Digraph::from_json("{\"nodes\": [{\"id\": 0}],\n \"edges\": [\n{\"source\": 0, \"target\": 1}]}")
+        .err()
+        .unwrap();
+    assert_eq!("line 3: unknown vertex \"1\"", err.to_string());
+
+    let err = EdgeWeightedGraph::from_json("{\"nodes\": [{\"id\": 0}], \"edges\": [{\"source\": 0, \"target\": 0}]}")
+        .err()
+        .unwrap();
+    assert_eq!("line 1: missing weight", err.to_string());
+
+    let err = Graph::from_json("{\"nodes\": [{\"id\": 0}, {\"id\": 0}], \"edges\": []}")
+        .err()
+        .unwrap();
+    assert_eq!("line 1: duplicate vertex \"0\"", err.to_string());
+
+    let err = Graph::from_json("{\"nodes\": [\n{\"id\": 0}\n\"edges\": []}")
+        .err()
+        .unwrap();
+    assert_eq!("line 3: expected ',' or ']'", err.to_string());
+
+    let err = Graph::from_json("{\"nodes\": []").err().unwrap();
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::UnexpectedEof => true,
+        _ => false,
+    });
+    assert!(Graph::from_json("{\"nodes\": []}").is_err());
+    assert!(Graph::from_json("{\"nodes\": [], \"edges\": []} []").is_err());
+
+    let nested = |depth: usize| {
+        let label = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        format!("{{\"nodes\": [{{\"id\": 0, \"label\": {}}}], \"edges\": []}}", label)
+    };
+    assert!(Graph::from_json(&nested(100)).is_ok());
+    let err = Graph::from_json(&nested(200)).err().unwrap();
+    assert_eq!("line 1: nested deeper than 128 levels", err.to_string());
+    let err = Graph::from_json(&"[".repeat(200_000)).err().unwrap();
+    assert!(match *err.kind() {
+        ParseGraphErrorKind::Syntax(_) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn test_json_non_finite_weights() {
+    let mut g = EdgeWeightedDigraph::new(2);
+    g.add_edge(DirectedEdge::new(0, 1, f64::INFINITY));
+    g.add_edge(DirectedEdge::new(1, 0, f64::NEG_INFINITY));
+    let json = g.to_json();
+    assert!(json.contains("\"weight\": \"inf\"") && json.contains("\"weight\": \"-inf\""));
+    let h = EdgeWeightedDigraph::from_json(&json).unwrap();
+    let mut weights = h.all_edges().iter().map(|e| (e.from(), e.weight())).collect::<Vec<_>>();
+    weights.sort_by_key(|w| w.0);
+    assert_eq!(vec![(0, f64::INFINITY), (1, f64::NEG_INFINITY)], weights);
+
+    // edges cannot weigh NaN, and other strings are not weights
+    let err = EdgeWeightedDigraph::from_json(&json.replace("-inf", "NaN"))
+        .err()
+        .unwrap();
+    assert!(err.to_string().ends_with("invalid weight \"NaN\""));
+    let err = EdgeWeightedDigraph::from_json(&json.replace("-inf", "1.5"))
+        .err()
+        .unwrap();
+    assert!(err.to_string().ends_with("invalid weight \"not a number\""));
+}
@@ -0,0 +1,208 @@
+use super::directed::TarjanSCC;
+use super::traversal::{DepthFirstOrder, SearchPaths};
+use super::view::GraphView;
+use super::{Digraph, StronglyConnectedComponents};
+use std::iter;
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+
+/// A fixed-size set of `0..n`, one bit per element.
+#[derive(Clone, Debug)]
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(n: usize) -> BitSet {
+        BitSet {
+            words: iter::repeat(0).take(n.div_ceil(64)).collect(),
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn union_with(&mut self, other: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= *b;
+        }
+    }
+}
+
+/// All-pairs reachability by a depth-first search from every vertex, using `O(V^2)` space.
+pub struct TransitiveClosure<'a, G: 'a = Digraph> {
+    all: Vec<SearchPaths<'a, G>>,
+}
+
+impl<'a, G: GraphView> TransitiveClosure<'a, G> {
+    pub fn new(graph: &'a G) -> TransitiveClosure<'a, G> {
+        TransitiveClosure {
+            all: (0..graph.vertex_count())
+                .map(|v| SearchPaths::depth_first(graph, iter::once(v)))
+                .collect(),
+        }
+    }
+
+    /// Is there a directed path from `v` to `w`? Every vertex reaches itself.
+    pub fn reachable(&self, v: usize, w: usize) -> bool {
+        self.all[v].has_path_to(w)
+    }
+
+    /// A directed path from `v` to `w`, if there is one.
+    pub fn path(&self, v: usize, w: usize) -> Option<Vec<usize>> {
+        self.all[v].path_to(w)
+    }
+}
+
+/// All-pairs reachability over the strongly-connected components: vertices of one component reach the same
+/// vertices, so only one row of bits per component is stored, `O(C^2 / 64)` words for `C` components.
+pub struct CondensedClosure {
+    id: Vec<usize>,
+    rows: Vec<BitSet>,
+}
+
+impl CondensedClosure {
+    pub fn new<G: GraphView>(graph: &G) -> CondensedClosure {
+        let scc = TarjanSCC::new(graph);
+        let dag = scc.condensation();
+        let mut rows = iter::repeat(BitSet::new(scc.count()))
+            .take(scc.count())
+            .collect::<Vec<BitSet>>();
+        // in postorder, every component comes after all the components it reaches
+        for &c in DepthFirstOrder::new(&dag).post() {
+            let mut row = BitSet::new(scc.count());
+            row.insert(c);
+            for d in dag.adj(c) {
+                row.union_with(&rows[d]);
+            }
+            rows[c] = row;
+        }
+        CondensedClosure {
+            id: (0..graph.vertex_count()).map(|v| scc.id(v)).collect(),
+            rows: rows,
+        }
+    }
+
+    /// Number of strongly-connected components.
+    pub fn count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Is there a directed path from `v` to `w`? Every vertex reaches itself.
+    pub fn reachable(&self, v: usize, w: usize) -> bool {
+        self.rows[self.id[v]].contains(self.id[w])
+    }
+}
+
+/// The transitive reduction of a DAG: the fewest edges with the same reachability, which for a DAG are exactly
+/// the edges `v -> w` with no other path from `v` to `w`. Returns a directed cycle if `graph` is not a DAG.
+pub fn transitive_reduction(graph: &Digraph) -> Result<Digraph, Vec<usize>> {
+    let topo = graph.topological_order()?;
+    let mut reduction = Digraph::new(graph.v());
+    let mut reach = iter::repeat(BitSet::new(graph.v()))
+        .take(graph.v())
+        .collect::<Vec<BitSet>>();
+    for &v in topo.order().iter().rev() {
+        // nearer successors first: a later one cannot reach an earlier one
+        let mut succ = graph.adj(v);
+        succ.sort_by_key(|&w| topo.rank(w));
+        succ.dedup();
+        let mut covered = BitSet::new(graph.v());
+        for w in succ {
+            if !covered.contains(w) {
+                reduction.add_edge(v, w);
+                covered.union_with(&reach[w]);
+            }
+        }
+        covered.insert(v);
+        reach[v] = covered;
+    }
+    Ok(reduction)
+}
+
+#[cfg(test)]
+fn tiny_dg() -> Digraph {
+    "13 22\n4 2\n2 3\n3 2\n6 0\n0 1\n2 0\n11 12\n12 9\n9 10\n9 11\n7 9\n10 12\n11 4\n4 3\n3 5\n6 8\n\
+     8 6\n5 4\n0 5\n6 4\n6 9\n7 6"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_transitive_closure() {
+    let g = tiny_dg();
+    let tc = g.transitive_closure();
+    let cc = g.condensed_closure();
+    assert_eq!(5, cc.count());
+    for v in 0..g.v() {
+        for w in 0..g.v() {
+            assert_eq!(tc.reachable(v, w), cc.reachable(v, w));
+        }
+    }
+    assert!(tc.reachable(7, 1));
+    assert!(!tc.reachable(1, 7));
+    assert!(cc.reachable(1, 1));
+    assert!(!cc.reachable(0, 6));
+    assert_eq!(Some(vec![1]), tc.path(1, 1));
+    assert_eq!(None, tc.path(2, 6));
+
+    let path = tc.path(6, 3).unwrap();
+    assert_eq!((Some(&6), Some(&3)), (path.first(), path.last()));
+    assert!(path.windows(2).all(|e| g.has_edge(e[0], e[1])));
+
+    let mut bits = BitSet::new(130);
+    bits.insert(0);
+    bits.insert(129);
+    assert_eq!(3, bits.words.len());
+    assert!(bits.contains(0) && bits.contains(129) && !bits.contains(128));
+}
+
+#[test]
+fn test_transitive_reduction() {
+    let g: Digraph = "5 7\n0 1\n1 2\n0 2\n2 3\n0 3\n1 3\n0 1".parse().unwrap();
+    let r = g.transitive_reduction().unwrap();
+    let mut edges = r.all_edges();
+    edges.sort();
+    assert_eq!(vec![(0, 1), (1, 2), (2, 3)], edges);
+    assert_eq!(5, r.v());
+
+    assert!(tiny_dg().transitive_reduction().is_err());
+    assert_eq!(0, Digraph::new(0).transitive_reduction().unwrap().v());
+}
+
+#[test]
+fn quicktest_closure() {
+    fn prop(g: Digraph) -> bool {
+        let tc = g.transitive_closure();
+        let cc = g.condensed_closure();
+        (0..g.v()).all(|v| (0..g.v()).all(|w| tc.reachable(v, w) == cc.reachable(v, w)))
+    }
+    quickcheck(prop as fn(Digraph) -> bool);
+
+    fn prop_reduction(g: Digraph) -> bool {
+        // keep the edges pointing forward, to get a DAG
+        let mut dag = Digraph::new(g.v());
+        for (v, w) in g.all_edges() {
+            if v < w {
+                dag.add_edge(v, w);
+            }
+        }
+        let r = dag.transitive_reduction().unwrap();
+        let (before, after) = (dag.condensed_closure(), r.condensed_closure());
+        let same = (0..g.v()).all(|v| (0..g.v()).all(|w| before.reachable(v, w) == after.reachable(v, w)));
+        // dropping any edge of the reduction loses some reachability
+        let minimal = r.all_edges().into_iter().all(|(v, w)| {
+            let mut smaller = r.clone();
+            smaller.remove_edge(v, w);
+            !smaller.condensed_closure().reachable(v, w)
+        });
+        same && minimal
+    }
+    quickcheck(prop_reduction as fn(Digraph) -> bool);
+}
@@ -0,0 +1,464 @@
+use super::super::hashst::HashST;
+use super::directed::{Digraph, StronglyConnectedComponents};
+use super::euler::EulerianError;
+use super::traversal::SearchPaths;
+use super::undirected::Graph;
+use super::view::GraphView;
+use std::hash::Hash;
+use std::io;
+use std::io::BufRead;
+
+/// Vertex names, mapped to and from the dense indices `0..n` of the underlying graph.
+struct SymbolTable<K> {
+    st: HashST<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> SymbolTable<K> {
+    fn new() -> SymbolTable<K> {
+        SymbolTable {
+            st: HashST::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, key: K) -> usize {
+        if let Some(&v) = self.st.get(&key) {
+            return v;
+        }
+        let v = self.keys.len();
+        self.st.put(key.clone(), v);
+        self.keys.push(key);
+        v
+    }
+
+    /// Index the names of the given edges.
+    fn index_edges<I: IntoIterator<Item = (K, K)>>(&mut self, edges: I) -> Vec<(usize, usize)> {
+        edges
+            .into_iter()
+            .map(|(v, w)| (self.insert(v), self.insert(w)))
+            .collect()
+    }
+
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.st.get(key).cloned()
+    }
+
+    fn names<I: IntoIterator<Item = usize>>(&self, vs: I) -> Vec<&K> {
+        vs.into_iter().map(|v| &self.keys[v]).collect()
+    }
+}
+
+/// Read lines of `from/to1/to2...`, with `delimiter` in place of `/`, as edges from the first name to each
+/// of the others, adding every name to `st`. A line of a single name is a vertex without edges.
+fn read_adjacency_lists<R: BufRead>(
+    reader: R,
+    delimiter: &str,
+    st: &mut SymbolTable<String>,
+) -> io::Result<Vec<(usize, usize)>> {
+    let mut edges = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        // `lines` leaves the `\r` of a CRLF line ending
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        let mut names = line.split(delimiter);
+        let from = st.insert(names.next().unwrap().to_owned());
+        for to in names {
+            edges.push((from, st.insert(to.to_owned())));
+        }
+    }
+    Ok(edges)
+}
+
+/// Paths from the source(s) of a search, reported by name.
+pub struct SymbolPaths<'a, K: 'a, G: 'a> {
+    st: &'a SymbolTable<K>,
+    paths: SearchPaths<'a, G>,
+}
+
+impl<'a, K: Hash + Eq + Clone, G: GraphView> SymbolPaths<'a, K, G> {
+    /// Is there a path to `key`? Unknown names are never reached.
+    pub fn has_path_to(&self, key: &K) -> bool {
+        match self.st.index_of(key) {
+            Some(v) => self.paths.has_path_to(v),
+            None => false,
+        }
+    }
+
+    pub fn path_to(&self, key: &K) -> Option<Vec<&'a K>> {
+        let v = self.st.index_of(key)?;
+        self.paths.path_to(v).map(|path| self.st.names(path))
+    }
+}
+
+/// A graph whose vertices are named by arbitrary keys.
+pub struct SymbolGraph<K> {
+    st: SymbolTable<K>,
+    graph: Graph,
+}
+
+impl<K: Hash + Eq + Clone> SymbolGraph<K> {
+    /// Build the graph of the given edges, the vertices being every name that appears.
+    pub fn from_edges<I: IntoIterator<Item = (K, K)>>(edges: I) -> SymbolGraph<K> {
+        let mut st = SymbolTable::new();
+        let edges = st.index_edges(edges);
+        SymbolGraph::with_edges(st, edges)
+    }
+
+    fn with_edges(st: SymbolTable<K>, edges: Vec<(usize, usize)>) -> SymbolGraph<K> {
+        let mut graph = Graph::new(st.keys.len());
+        for (v, w) in edges {
+            graph.add_edge(v, w);
+        }
+        SymbolGraph { st: st, graph: graph }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.st.index_of(key).is_some()
+    }
+
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.st.index_of(key)
+    }
+
+    pub fn name_of(&self, v: usize) -> &K {
+        &self.st.keys[v]
+    }
+
+    /// The underlying graph.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Names adjacent to `key`, `None` if there is no such vertex.
+    pub fn adj(&self, key: &K) -> Option<Vec<&K>> {
+        let v = self.st.index_of(key)?;
+        Some(self.st.names(self.graph.adj(v).cloned()))
+    }
+
+    pub fn degree(&self, key: &K) -> Option<usize> {
+        self.st.index_of(key).map(|v| self.graph.degree(v))
+    }
+
+    pub fn dfs(&self, key: &K) -> Option<SymbolPaths<K, Graph>> {
+        let s = self.st.index_of(key)?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.dfs(s),
+        })
+    }
+
+    /// Paths from any of `keys`, `None` if one of them is not a vertex.
+    pub fn dfs_multi_source<'a, T: IntoIterator<Item = &'a K>>(&self, keys: T) -> Option<SymbolPaths<K, Graph>>
+    where
+        K: 'a,
+    {
+        let sources = keys
+            .into_iter()
+            .map(|k| self.st.index_of(k))
+            .collect::<Option<Vec<usize>>>()?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.dfs_multi_source(sources),
+        })
+    }
+
+    pub fn bfs(&self, key: &K) -> Option<SymbolPaths<K, Graph>> {
+        let s = self.st.index_of(key)?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.bfs(s),
+        })
+    }
+
+    /// Names of the vertices of each connected component.
+    pub fn connected_components(&self) -> Vec<Vec<&K>> {
+        let cc = self.graph.connected_components();
+        let mut components = (0..cc.count()).map(|_| Vec::new()).collect::<Vec<Vec<&K>>>();
+        for v in 0..self.graph.vertices() {
+            components[cc.id(v)].push(self.name_of(v));
+        }
+        components
+    }
+
+    pub fn cycle(&self) -> Option<Vec<&K>> {
+        self.graph.cycle().cycle().map(|c| self.st.names(c.iter().cloned()))
+    }
+
+    /// Names in each colour class, or the names along an odd-length cycle.
+    pub fn bipartition(&self) -> Result<(Vec<&K>, Vec<&K>), Vec<&K>> {
+        match self.graph.bipartition() {
+            Ok((left, right)) => Ok((self.st.names(left), self.st.names(right))),
+            Err(cycle) => Err(self.st.names(cycle)),
+        }
+    }
+
+    /// Pairs of names joined by a bridge.
+    pub fn bridges(&self) -> Vec<(&K, &K)> {
+        self.edge_names(self.graph.biconnectivity().bridges().iter().cloned())
+    }
+
+    pub fn articulation_points(&self) -> Vec<&K> {
+        self.st.names(self.graph.biconnectivity().articulation_points())
+    }
+
+    pub fn eulerian_cycle(&self) -> Result<Vec<(&K, &K)>, EulerianError> {
+        self.graph.eulerian_cycle().map(|edges| self.edge_names(edges))
+    }
+
+    pub fn eulerian_path(&self) -> Result<Vec<(&K, &K)>, EulerianError> {
+        self.graph.eulerian_path().map(|edges| self.edge_names(edges))
+    }
+
+    pub fn hamiltonian_path(&self) -> Option<Vec<&K>> {
+        self.graph.hamiltonian_path().map(|path| self.st.names(path))
+    }
+
+    fn edge_names<I: IntoIterator<Item = (usize, usize)>>(&self, edges: I) -> Vec<(&K, &K)> {
+        edges
+            .into_iter()
+            .map(|(v, w)| (self.name_of(v), self.name_of(w)))
+            .collect()
+    }
+}
+
+impl SymbolGraph<String> {
+    /// Read lines of `from/to1/to2...`, with `delimiter` in place of `/`, connecting the first name to each of
+    /// the others.
+    pub fn from_reader<R: BufRead>(reader: R, delimiter: &str) -> io::Result<SymbolGraph<String>> {
+        let mut st = SymbolTable::new();
+        let edges = read_adjacency_lists(reader, delimiter, &mut st)?;
+        Ok(SymbolGraph::with_edges(st, edges))
+    }
+}
+
+/// A digraph whose vertices are named by arbitrary keys.
+pub struct SymbolDigraph<K> {
+    st: SymbolTable<K>,
+    graph: Digraph,
+}
+
+impl<K: Hash + Eq + Clone> SymbolDigraph<K> {
+    /// Build the digraph of the given edges, the vertices being every name that appears.
+    pub fn from_edges<I: IntoIterator<Item = (K, K)>>(edges: I) -> SymbolDigraph<K> {
+        let mut st = SymbolTable::new();
+        let edges = st.index_edges(edges);
+        SymbolDigraph::with_edges(st, edges)
+    }
+
+    fn with_edges(st: SymbolTable<K>, edges: Vec<(usize, usize)>) -> SymbolDigraph<K> {
+        let mut graph = Digraph::new(st.keys.len());
+        for (v, w) in edges {
+            graph.add_edge(v, w);
+        }
+        SymbolDigraph { st: st, graph: graph }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.st.index_of(key).is_some()
+    }
+
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.st.index_of(key)
+    }
+
+    pub fn name_of(&self, v: usize) -> &K {
+        &self.st.keys[v]
+    }
+
+    /// The underlying digraph.
+    pub fn digraph(&self) -> &Digraph {
+        &self.graph
+    }
+
+    /// Names `key` points to, `None` if there is no such vertex.
+    pub fn adj(&self, key: &K) -> Option<Vec<&K>> {
+        let v = self.st.index_of(key)?;
+        Some(self.st.names(self.graph.adj(v)))
+    }
+
+    pub fn outdegree(&self, key: &K) -> Option<usize> {
+        self.st.index_of(key).map(|v| self.graph.outdegree(v))
+    }
+
+    pub fn dfs(&self, key: &K) -> Option<SymbolPaths<K, Digraph>> {
+        let s = self.st.index_of(key)?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.dfs(s),
+        })
+    }
+
+    /// Paths from any of `keys`, `None` if one of them is not a vertex.
+    pub fn dfs_multi_source<'a, T: IntoIterator<Item = &'a K>>(&self, keys: T) -> Option<SymbolPaths<K, Digraph>>
+    where
+        K: 'a,
+    {
+        let sources = keys
+            .into_iter()
+            .map(|k| self.st.index_of(k))
+            .collect::<Option<Vec<usize>>>()?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.dfs_multi_source(sources),
+        })
+    }
+
+    pub fn bfs(&self, key: &K) -> Option<SymbolPaths<K, Digraph>> {
+        let s = self.st.index_of(key)?;
+        Some(SymbolPaths {
+            st: &self.st,
+            paths: self.graph.bfs(s),
+        })
+    }
+
+    pub fn reverse_dfs_postorder(&self) -> Vec<&K> {
+        self.st.names(self.graph.reverse_dfs_postorder())
+    }
+
+    /// Names in topological order, or the names along a directed cycle.
+    pub fn topological_order(&self) -> Result<Vec<&K>, Vec<&K>> {
+        match self.graph.topological_order() {
+            Ok(topo) => Ok(self.st.names(topo.order().iter().cloned())),
+            Err(cycle) => Err(self.st.names(cycle)),
+        }
+    }
+
+    pub fn directed_cycle(&self) -> Option<Vec<&K>> {
+        self.graph
+            .directed_cycle()
+            .cycle()
+            .map(|c| self.st.names(c.iter().cloned()))
+    }
+
+    /// Names of the vertices of each strongly-connected component.
+    pub fn strong_components(&self) -> Vec<Vec<&K>> {
+        self.graph
+            .tarjan_scc()
+            .components()
+            .into_iter()
+            .map(|c| self.st.names(c))
+            .collect()
+    }
+}
+
+impl SymbolDigraph<String> {
+    /// Read lines of `from/to1/to2...`, with `delimiter` in place of `/`, as edges from the first name to each
+    /// of the others.
+    pub fn from_reader<R: BufRead>(reader: R, delimiter: &str) -> io::Result<SymbolDigraph<String>> {
+        let mut st = SymbolTable::new();
+        let edges = read_adjacency_lists(reader, delimiter, &mut st)?;
+        Ok(SymbolDigraph::with_edges(st, edges))
+    }
+}
+
+#[cfg(test)]
+const ROUTES: &str = "JFK MCO\nORD DEN\nORD HOU\nDFW PHX\nJFK ATL\nORD DFW\nORD PHX\nATL HOU\nDEN PHX\n\
+                      PHX LAX\nJFK ORD\nDEN LAS\nDFW HOU\nORD ATL\nLAS LAX\nATL MCO\nHOU MCO\nLAS PHX\n";
+
+#[test]
+fn test_symbol_graph() {
+    let sg = SymbolGraph::from_reader(ROUTES.as_bytes(), " ").unwrap();
+    let name = |s: &str| s.to_owned();
+
+    assert_eq!(10, sg.graph().vertices());
+    assert_eq!(18, sg.graph().edges());
+    assert_eq!(Some(0), sg.index_of(&name("JFK")));
+    assert_eq!("MCO", sg.name_of(1));
+    assert!(!sg.contains(&name("SFO")));
+
+    let mut adj = sg.adj(&name("JFK")).unwrap();
+    adj.sort();
+    assert_eq!(vec!["ATL", "MCO", "ORD"], adj);
+    assert_eq!(Some(3), sg.degree(&name("JFK")));
+
+    let path = sg.bfs(&name("JFK")).unwrap().path_to(&name("LAS")).unwrap();
+    assert_eq!(4, path.len());
+    assert_eq!(("JFK", "LAS"), (path[0].as_str(), path[3].as_str()));
+    let paths = sg.dfs(&name("LAX")).unwrap();
+    assert!(paths.has_path_to(&name("MCO")));
+    assert!(!paths.has_path_to(&name("SFO")));
+    assert_eq!(None, paths.path_to(&name("SFO")));
+
+    assert_eq!(1, sg.connected_components().len());
+    assert!(sg.cycle().is_some());
+    let odd = sg.bipartition().err().unwrap();
+    assert!(odd.len() % 2 == 0 && odd.first() == odd.last());
+    assert!(odd
+        .iter()
+        .zip(odd.iter().skip(1))
+        .all(|(v, w)| sg.adj(v).unwrap().contains(w)));
+    assert!(sg.bridges().is_empty() && sg.articulation_points().is_empty());
+    let path = sg.hamiltonian_path().unwrap();
+    assert_eq!(10, path.len());
+    assert!(path
+        .iter()
+        .zip(path.iter().skip(1))
+        .all(|(v, w)| sg.adj(v).unwrap().contains(w)));
+
+    // unknown names are not vertices to search from
+    assert!(sg.adj(&name("SFO")).is_none() && sg.degree(&name("SFO")).is_none());
+    assert!(sg.dfs(&name("SFO")).is_none() && sg.bfs(&name("SFO")).is_none());
+    assert!(sg.dfs_multi_source(&[name("JFK"), name("SFO")]).is_none());
+
+    // a line of one name is an isolated vertex, and CRLF line endings are not part of the names
+    let sg = SymbolGraph::from_reader("JFK/MCO\r\nSFO\r\nMCO/ATL\r\n".as_bytes(), "/").unwrap();
+    assert_eq!(4, sg.graph().vertices());
+    assert_eq!(Some(0), sg.degree(&name("SFO")));
+    assert_eq!(Some(vec![&name("ATL"), &name("JFK")]), sg.adj(&name("MCO")));
+    assert_eq!(2, sg.connected_components().len());
+    let (left, right) = sg.bipartition().unwrap();
+    let (mco, others) = if left.len() == 1 { (left, right) } else { (right, left) };
+    assert_eq!(vec![&name("MCO")], mco);
+    assert_eq!(3, others.len());
+    let mut bridges = sg
+        .bridges()
+        .into_iter()
+        .map(|(v, w)| if v < w { (v, w) } else { (w, v) })
+        .collect::<Vec<_>>();
+    bridges.sort();
+    assert_eq!(vec![(&name("ATL"), &name("MCO")), (&name("JFK"), &name("MCO"))], bridges);
+    assert_eq!(vec![&name("MCO")], sg.articulation_points());
+    assert_eq!(2, sg.eulerian_path().unwrap().len());
+    assert!(sg.eulerian_cycle().is_err() && sg.hamiltonian_path().is_none());
+}
+
+#[test]
+fn test_symbol_digraph() {
+    let input = "cargo/rustc/serde\nserde/serde_derive\nserde_derive/syn/quote\nsyn/quote\nrustc\n";
+    let sg = SymbolDigraph::from_reader(input.as_bytes(), "/").unwrap();
+    let name = |s: &str| s.to_owned();
+
+    assert_eq!(6, sg.digraph().v());
+    assert_eq!(6, sg.digraph().e());
+    assert_eq!(Some(2), sg.outdegree(&name("serde_derive")));
+
+    let order = sg.topological_order().unwrap();
+    assert_eq!("cargo", order[0]);
+    let rank = |s: &str| order.iter().position(|&n| n == s).unwrap();
+    assert!(rank("syn") < rank("quote"));
+    assert_eq!(order, sg.reverse_dfs_postorder());
+    assert!(sg.directed_cycle().is_none());
+
+    let path = sg.dfs(&name("cargo")).unwrap().path_to(&name("quote")).unwrap();
+    assert_eq!(&"cargo", path.first().unwrap());
+    assert_eq!(&"quote", path.last().unwrap());
+    let sources = vec![name("syn"), name("rustc")];
+    assert!(!sg.dfs_multi_source(&sources).unwrap().has_path_to(&name("serde")));
+    assert!(sg.bfs(&name("serde")).unwrap().has_path_to(&name("quote")));
+    assert!(sg.bfs(&name("tokio")).is_none() && sg.outdegree(&name("tokio")).is_none());
+
+    // generic keys and cycles
+    let sg = SymbolDigraph::from_edges(vec![(10, 20), (20, 30), (30, 10), (30, 40)]);
+    let cycle = sg.directed_cycle().unwrap();
+    assert_eq!(4, cycle.len());
+    assert_eq!(cycle[0], cycle[3]);
+    assert!(!cycle.contains(&&40));
+    assert!(sg.topological_order().is_err());
+    let mut components = sg.strong_components();
+    components.sort();
+    assert_eq!(2, components.len());
+}
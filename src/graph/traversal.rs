@@ -0,0 +1,237 @@
+use super::super::queue::Queue;
+use super::super::stack::Stack;
+use super::view::GraphView;
+use super::SearchSource;
+use std::iter;
+
+/// Paths found by a depth-first or breadth-first search, in any `GraphView`.
+pub struct SearchPaths<'a, G: 'a> {
+    graph: &'a G,
+    marked: Vec<bool>,
+    edge_to: Vec<Option<usize>>,
+    source: SearchSource,
+}
+
+impl<'a, G: GraphView> SearchPaths<'a, G> {
+    /// Depth-first search from each of `sources` in turn.
+    pub fn depth_first<T: IntoIterator<Item = usize>>(graph: &'a G, sources: T) -> SearchPaths<'a, G> {
+        let mut path = SearchPaths::new(graph, SearchSource::Multi(sources.into_iter().collect()));
+        path.dfs();
+        path
+    }
+
+    /// Breadth-first search from all of `sources` at once, finding shortest paths.
+    pub fn breadth_first<T: IntoIterator<Item = usize>>(graph: &'a G, sources: T) -> SearchPaths<'a, G> {
+        let mut path = SearchPaths::new(graph, SearchSource::Multi(sources.into_iter().collect()));
+        path.bfs();
+        path
+    }
+
+    fn new(graph: &'a G, source: SearchSource) -> SearchPaths<'a, G> {
+        let mut marked = iter::repeat(false).take(graph.vertex_count()).collect::<Vec<bool>>();
+        let edge_to = iter::repeat(None).take(graph.vertex_count()).collect();
+        for s in source.iter() {
+            marked[s] = true;
+        }
+
+        SearchPaths {
+            graph: graph,
+            marked: marked,
+            edge_to: edge_to,
+            source: source,
+        }
+    }
+
+    fn dfs_from(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.marked[s] = true;
+        stack.push((s, graph.neighbors(s)));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(w) if !self.marked[w] => {
+                    self.marked[w] = true;
+                    self.edge_to[w] = Some(v);
+                    stack.push((w, graph.neighbors(w)));
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    fn dfs(&mut self) {
+        for v in self.source.iter() {
+            self.dfs_from(v);
+        }
+    }
+
+    fn bfs(&mut self) {
+        let mut q = Queue::new();
+        for s in self.source.iter() {
+            q.enqueue(s);
+        }
+        while !q.is_empty() {
+            let v = q.dequeue().unwrap();
+            for w in self.graph.neighbors(v) {
+                if !self.marked[w] {
+                    self.edge_to[w] = Some(v);
+                    q.enqueue(w);
+                    self.marked[w] = true;
+                }
+            }
+        }
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if self.has_path_to(v) {
+            let mut path = Stack::new();
+            let mut x = v;
+            while !self.source.contains(x) {
+                path.push(x);
+                x = self.edge_to[x].unwrap();
+            }
+            path.push(x);
+            Some(path.into_iter().collect())
+        } else {
+            None
+        }
+    }
+}
+
+/// Preorder and postorder of the vertices in a depth-first search of the whole graph.
+pub struct DepthFirstOrder {
+    pre: Vec<usize>,
+    post: Vec<usize>,
+}
+
+impl DepthFirstOrder {
+    pub fn new<G: GraphView>(graph: &G) -> DepthFirstOrder {
+        let mut marked = iter::repeat(false).take(graph.vertex_count()).collect::<Vec<bool>>();
+        let mut order = DepthFirstOrder {
+            pre: Vec::with_capacity(graph.vertex_count()),
+            post: Vec::with_capacity(graph.vertex_count()),
+        };
+        for v in 0..graph.vertex_count() {
+            if !marked[v] {
+                order.dfs(graph, &mut marked, v);
+            }
+        }
+        order
+    }
+
+    fn dfs<G: GraphView>(&mut self, graph: &G, marked: &mut [bool], s: usize) {
+        let mut stack = Stack::new();
+        marked[s] = true;
+        self.pre.push(s);
+        stack.push((s, graph.neighbors(s)));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(w) if !marked[w] => {
+                    marked[w] = true;
+                    self.pre.push(w);
+                    stack.push((w, graph.neighbors(w)));
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                    self.post.push(v);
+                }
+            }
+        }
+    }
+
+    pub fn pre(&self) -> &[usize] {
+        &self.pre
+    }
+
+    pub fn post(&self) -> &[usize] {
+        &self.post
+    }
+
+    /// Reverse postorder, a topological order if the graph is a DAG.
+    pub fn reverse_post(&self) -> Vec<usize> {
+        self.post.iter().rev().cloned().collect()
+    }
+}
+
+/// The squares of a `width` by `height` board, adjacent to the squares up, down, left and right of them.
+#[cfg(test)]
+struct Grid {
+    width: usize,
+    height: usize,
+}
+
+#[cfg(test)]
+impl GraphView for Grid {
+    type Neighbors<'a> = ::std::vec::IntoIter<usize>;
+
+    fn vertex_count(&self) -> usize {
+        self.width * self.height
+    }
+
+    fn edge_count(&self) -> usize {
+        (self.width - 1) * self.height + self.width * (self.height - 1)
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        let (x, y) = (v % self.width, v / self.width);
+        let mut adj = Vec::with_capacity(4);
+        if x > 0 {
+            adj.push(v - 1);
+        }
+        if x + 1 < self.width {
+            adj.push(v + 1);
+        }
+        if y > 0 {
+            adj.push(v - self.width);
+        }
+        if y + 1 < self.height {
+            adj.push(v + self.width);
+        }
+        adj.into_iter()
+    }
+}
+
+#[test]
+fn test_search_implicit_graph() {
+    let grid = Grid { width: 4, height: 3 };
+    let paths = SearchPaths::breadth_first(&grid, vec![0]);
+    assert_eq!(6, paths.path_to(11).unwrap().len());
+    assert_eq!(Some(vec![0, 1]), paths.path_to(1));
+
+    let paths = SearchPaths::depth_first(&grid, vec![0, 11]);
+    assert!((0..12).all(|v| paths.has_path_to(v)));
+    assert_eq!(Some(vec![11]), paths.path_to(11));
+
+    let order = DepthFirstOrder::new(&grid);
+    assert_eq!(12, order.pre().len());
+    assert_eq!(0, order.pre()[0]);
+    assert_eq!(Some(&0), order.post().last());
+    assert_eq!(0, order.reverse_post()[0]);
+}
+
+#[test]
+fn test_search_directed_and_undirected() {
+    use super::{Digraph, Graph};
+
+    let edges = "4 3\n0 1\n1 2\n3 2\n";
+    let g: Graph = edges.parse().unwrap();
+    let d: Digraph = edges.parse().unwrap();
+
+    assert_eq!(Some(vec![0, 1, 2, 3]), SearchPaths::breadth_first(&g, vec![0]).path_to(3));
+    assert_eq!(None, SearchPaths::breadth_first(&d, vec![0]).path_to(3));
+    assert_eq!(Some(vec![3, 2]), SearchPaths::depth_first(&d, vec![3, 0]).path_to(2));
+
+    assert_eq!(vec![0, 1, 2, 3], DepthFirstOrder::new(&g).pre());
+    assert_eq!(vec![2, 1, 0, 3], DepthFirstOrder::new(&d).post());
+    assert_eq!(vec![3, 0, 1, 2], DepthFirstOrder::new(&d).reverse_post());
+}
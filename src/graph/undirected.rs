@@ -1,7 +1,20 @@
 use super::super::bag;
 use super::super::bag::Bag;
+use super::super::stack::Stack;
+#[cfg(test)]
+use super::super::union_find::UnionFind;
+use super::euler;
+pub use super::euler::EulerianError;
+use super::hamiltonian;
+use super::matching::{self, BipartiteMatching};
+pub use super::traversal::SearchPaths;
+use super::view::GraphView;
+use std::cmp;
 use std::iter;
 
+#[cfg(test)]
+use quickcheck::quickcheck;
+
 #[derive(Clone, Debug)]
 pub struct Graph {
     v: usize,
@@ -44,6 +57,68 @@ impl Graph {
         self.adj[v].len()
     }
 
+    /// Add an isolated vertex, returning its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Bag::new());
+        self.v += 1;
+        self.v - 1
+    }
+
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.adj[v].iter().any(|&x| x == w)
+    }
+
+    /// Remove one of the edges between `v` and `w`, returning whether there was one.
+    pub fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+
+        if self.adj[v].remove_first(|&x| x == w).is_none() {
+            return false;
+        }
+        // the other half of a self-loop is in adj[v] as well
+        self.adj[w].remove_first(|&x| x == v);
+        self.e -= 1;
+        true
+    }
+
+    /// Remove `v` and its edges. As with `Vec::swap_remove`, the last vertex takes over the index `v`, so that
+    /// vertices remain numbered from 0 to `vertices() - 1`.
+    pub fn remove_vertex(&mut self, v: usize) {
+        self.validate_vertex(v);
+        let last = self.v - 1;
+
+        // the edges of the last vertex are now in adj[v]
+        let removed = self.adj.swap_remove(v);
+        let mut self_loops = 0;
+        for &w in removed.iter() {
+            if w == v {
+                self_loops += 1;
+            } else {
+                let w = if w == last { v } else { w };
+                self.adj[w].remove_first(|&x| x == v);
+            }
+        }
+        self.v -= 1;
+        self.e -= removed.len() - self_loops / 2;
+
+        if v != last {
+            let mut neighbors = self.adj[v].iter().cloned().collect::<Vec<usize>>();
+            neighbors.sort();
+            neighbors.dedup();
+            for w in neighbors {
+                let w = if w == last { v } else { w };
+                for x in self.adj[w].iter_mut() {
+                    if *x == last {
+                        *x = v;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn to_dot(&self) -> String {
         let mut dot = String::new();
 
@@ -73,6 +148,456 @@ impl Graph {
     pub fn adj(&self, v: usize) -> bag::Iter<usize> {
         self.adj[v].iter()
     }
+
+    /// All edges of the graph, each one reported once.
+    pub fn all_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::with_capacity(self.e);
+        for v in 0..self.v {
+            let mut self_loops = 0;
+            for &w in self.adj(v) {
+                if w > v {
+                    edges.push((v, w));
+                } else if w == v {
+                    // a self-loop appears twice in adj[v]
+                    if self_loops % 2 == 0 {
+                        edges.push((v, w));
+                    }
+                    self_loops += 1;
+                }
+            }
+        }
+        edges
+    }
+
+    /// The number of edges between each pair of vertices, a self-loop counting once.
+    pub fn adjacency_matrix(&self) -> Vec<Vec<usize>> {
+        let mut matrix = vec![vec![0; self.v]; self.v];
+        for (v, w) in self.all_edges() {
+            matrix[v][w] += 1;
+            if v != w {
+                matrix[w][v] += 1;
+            }
+        }
+        matrix
+    }
+
+    pub fn dfs(&self, s: usize) -> SearchPaths<Graph> {
+        SearchPaths::depth_first(self, iter::once(s))
+    }
+
+    pub fn dfs_multi_source<T: IntoIterator<Item = usize>>(&self, s: T) -> SearchPaths<Graph> {
+        SearchPaths::depth_first(self, s)
+    }
+
+    pub fn bfs(&self, s: usize) -> SearchPaths<Graph> {
+        SearchPaths::breadth_first(self, iter::once(s))
+    }
+
+    pub fn connected_components(&self) -> ConnectedComponents {
+        ConnectedComponents::new(self)
+    }
+
+    pub fn cycle(&self) -> Cycle {
+        Cycle::new(self)
+    }
+
+    /// Two-colour the graph, returning both colour classes, or an odd-length cycle if the graph is not bipartite.
+    pub fn bipartition(&self) -> Result<(Vec<usize>, Vec<usize>), Vec<usize>> {
+        let mut bp = Bipartite {
+            graph: self,
+            marked: iter::repeat(false).take(self.v).collect(),
+            color: iter::repeat(false).take(self.v).collect(),
+            edge_to: iter::repeat(None).take(self.v).collect(),
+            odd_cycle: None,
+        };
+        for v in 0..self.v {
+            if !bp.marked[v] && bp.odd_cycle.is_none() {
+                bp.dfs(v);
+            }
+        }
+        match bp.odd_cycle {
+            Some(cycle) => Err(cycle),
+            None => Ok((0..self.v).partition(|&v| !bp.color[v])),
+        }
+    }
+
+    /// Bridges, articulation points and biconnected components.
+    pub fn biconnectivity(&self) -> Biconnectivity {
+        Biconnectivity::new(self)
+    }
+
+    /// Edges of a cycle using every edge exactly once, by Hierholzer's algorithm.
+    pub fn eulerian_cycle(&self) -> Result<Vec<(usize, usize)>, EulerianError> {
+        euler::eulerian_cycle(self)
+    }
+
+    /// Edges of a path using every edge exactly once, by Hierholzer's algorithm.
+    pub fn eulerian_path(&self) -> Result<Vec<(usize, usize)>, EulerianError> {
+        euler::eulerian_path(self)
+    }
+
+    /// A path visiting every vertex exactly once. Only for small graphs, see `hamiltonian::hamiltonian_path`.
+    pub fn hamiltonian_path(&self) -> Option<Vec<usize>> {
+        hamiltonian::hamiltonian_path(self)
+    }
+
+    /// Maximum cardinality matching of a bipartite graph by the Hopcroft-Karp algorithm, or an odd-length cycle if
+    /// the graph is not bipartite.
+    pub fn hopcroft_karp(&self) -> Result<BipartiteMatching, Vec<usize>> {
+        matching::hopcroft_karp(self)
+    }
+}
+
+impl GraphView for Graph {
+    type Neighbors<'a> = iter::Cloned<bag::Iter<'a, usize>>;
+
+    fn vertex_count(&self) -> usize {
+        self.v
+    }
+
+    fn edge_count(&self) -> usize {
+        self.e
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.adj[v].iter().cloned()
+    }
+}
+
+/// The connected components of a graph.
+pub struct ConnectedComponents {
+    id: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl ConnectedComponents {
+    fn new(graph: &Graph) -> ConnectedComponents {
+        let n = graph.vertices();
+        let mut marked = iter::repeat(false).take(n).collect::<Vec<bool>>();
+        let mut id = iter::repeat(0).take(n).collect::<Vec<usize>>();
+        let mut size = Vec::new();
+
+        for s in 0..n {
+            if marked[s] {
+                continue;
+            }
+            let count = size.len();
+            size.push(0);
+            let mut stack = Stack::new();
+            marked[s] = true;
+            stack.push(s);
+            while let Some(v) = stack.pop() {
+                id[v] = count;
+                size[count] += 1;
+                for &w in graph.adj(v) {
+                    if !marked[w] {
+                        marked[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+
+        ConnectedComponents { id: id, size: size }
+    }
+
+    pub fn count(&self) -> usize {
+        self.size.len()
+    }
+
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Number of vertices in the component containing `v`.
+    pub fn size(&self, v: usize) -> usize {
+        self.size[self.id[v]]
+    }
+
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+}
+
+/// Find a cycle in a graph. Self-loops and parallel edges count as cycles.
+pub struct Cycle {
+    cycle: Option<Vec<usize>>,
+}
+
+impl Cycle {
+    fn new(graph: &Graph) -> Cycle {
+        let n = graph.vertices();
+        let mut finder = CycleFinder {
+            graph: graph,
+            marked: iter::repeat(false).take(n).collect(),
+            edge_to: iter::repeat(None).take(n).collect(),
+            cycle: None,
+        };
+        for v in 0..n {
+            if !finder.marked[v] && finder.cycle.is_none() {
+                finder.dfs(v);
+            }
+        }
+        Cycle { cycle: finder.cycle }
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// Vertices of the cycle, the first and the last one being the same.
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_ref().map(|c| &c[..])
+    }
+}
+
+struct CycleFinder<'a> {
+    graph: &'a Graph,
+    marked: Vec<bool>,
+    edge_to: Vec<Option<usize>>,
+    cycle: Option<Vec<usize>>,
+}
+
+impl<'a> CycleFinder<'a> {
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        // only one of the edges back to the parent is the tree edge, others are parallel edges
+        let mut stack = Stack::new();
+        self.marked[s] = true;
+        stack.push((s, graph.adj(s), false));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(&w) if !self.marked[w] => {
+                    self.marked[w] = true;
+                    self.edge_to[w] = Some(v);
+                    stack.push((w, graph.adj(w), false));
+                }
+                Some(&w) if Some(w) == self.edge_to[v] && !frame.2 => {
+                    frame.2 = true;
+                }
+                Some(&w) => {
+                    let mut cycle = Stack::new();
+                    cycle.push(w);
+                    let mut x = v;
+                    while x != w {
+                        cycle.push(x);
+                        x = self.edge_to[x].unwrap();
+                    }
+                    cycle.push(w);
+                    self.cycle = Some(cycle.into_iter().collect());
+                    return;
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Single points of failure of a graph, computed with Tarjan's low-link values in one depth-first search.
+pub struct Biconnectivity {
+    bridges: Vec<(usize, usize)>,
+    articulation: Vec<bool>,
+    components: Vec<Vec<(usize, usize)>>,
+}
+
+impl Biconnectivity {
+    fn new(graph: &Graph) -> Biconnectivity {
+        let n = graph.vertices();
+        let mut finder = LowLink {
+            graph: graph,
+            count: 0,
+            pre: iter::repeat(None).take(n).collect(),
+            low: iter::repeat(0).take(n).collect(),
+            edges: Stack::new(),
+            bc: Biconnectivity {
+                bridges: Vec::new(),
+                articulation: iter::repeat(false).take(n).collect(),
+                components: Vec::new(),
+            },
+        };
+        for v in 0..n {
+            if finder.pre[v].is_none() {
+                finder.dfs(v);
+            }
+        }
+        finder.bc
+    }
+
+    /// Edges whose removal disconnects their endpoints, as `(parent, child)` pairs of the DFS tree.
+    pub fn bridges(&self) -> &[(usize, usize)] {
+        &self.bridges
+    }
+
+    pub fn is_articulation_point(&self, v: usize) -> bool {
+        self.articulation[v]
+    }
+
+    /// Vertices whose removal increases the number of connected components.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        (0..self.articulation.len()).filter(|&v| self.articulation[v]).collect()
+    }
+
+    /// Partition of the edges into biconnected components. A self-loop forms a component of its own.
+    pub fn components(&self) -> &[Vec<(usize, usize)>] {
+        &self.components
+    }
+}
+
+struct LowLink<'a> {
+    graph: &'a Graph,
+    count: usize,
+    pre: Vec<Option<usize>>,
+    low: Vec<usize>,
+    edges: Stack<(usize, usize)>,
+    bc: Biconnectivity,
+}
+
+impl<'a> LowLink<'a> {
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.visit(s);
+        stack.push(LowLinkFrame::new(s, None, graph.adj(s)));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.v;
+            let pre_v = self.pre[v].unwrap();
+            let w = match frame.adj.next() {
+                Some(&w) => w,
+                None => {
+                    if frame.parent.is_none() && frame.children > 1 {
+                        self.bc.articulation[v] = true;
+                    }
+                    stack.pop();
+                    if let Some(parent) = stack.peek() {
+                        self.finish_child(parent.v, parent.parent, v);
+                    }
+                    continue;
+                }
+            };
+            match self.pre[w] {
+                None => {
+                    frame.children += 1;
+                    self.edges.push((v, w));
+                    self.visit(w);
+                    stack.push(LowLinkFrame::new(w, Some(v), graph.adj(w)));
+                }
+                Some(_) if w == v => {
+                    // a self-loop appears twice in adj[v]
+                    if frame.self_loops % 2 == 0 {
+                        self.bc.components.push(vec![(v, v)]);
+                    }
+                    frame.self_loops += 1;
+                }
+                Some(_) if Some(w) == frame.parent && !frame.skipped_parent => {
+                    frame.skipped_parent = true;
+                }
+                Some(pre_w) if pre_w < pre_v => {
+                    self.edges.push((v, w));
+                    self.low[v] = cmp::min(self.low[v], pre_w);
+                }
+                // the other end of a back edge already seen from the descendant
+                Some(_) => (),
+            }
+        }
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.pre[v] = Some(self.count);
+        self.low[v] = self.count;
+        self.count += 1;
+    }
+
+    /// Called once the subtree of the tree edge `v - w` has been explored.
+    fn finish_child(&mut self, v: usize, parent: Option<usize>, w: usize) {
+        let pre_v = self.pre[v].unwrap();
+        self.low[v] = cmp::min(self.low[v], self.low[w]);
+        if self.low[w] >= pre_v {
+            // v separates the subtree of w from the rest of the graph
+            if parent.is_some() {
+                self.bc.articulation[v] = true;
+            }
+            let mut component = Vec::new();
+            while let Some(e) = self.edges.pop() {
+                component.push(e);
+                if e == (v, w) {
+                    break;
+                }
+            }
+            self.bc.components.push(component);
+        }
+        if self.low[w] > pre_v {
+            self.bc.bridges.push((v, w));
+        }
+    }
+}
+
+struct LowLinkFrame<'a> {
+    v: usize,
+    parent: Option<usize>,
+    adj: bag::Iter<'a, usize>,
+    children: usize,
+    skipped_parent: bool,
+    self_loops: usize,
+}
+
+impl<'a> LowLinkFrame<'a> {
+    fn new(v: usize, parent: Option<usize>, adj: bag::Iter<'a, usize>) -> LowLinkFrame<'a> {
+        LowLinkFrame {
+            v: v,
+            parent: parent,
+            adj: adj,
+            children: 0,
+            skipped_parent: false,
+            self_loops: 0,
+        }
+    }
+}
+
+struct Bipartite<'a> {
+    graph: &'a Graph,
+    marked: Vec<bool>,
+    color: Vec<bool>,
+    edge_to: Vec<Option<usize>>,
+    odd_cycle: Option<Vec<usize>>,
+}
+
+impl<'a> Bipartite<'a> {
+    fn dfs(&mut self, s: usize) {
+        let graph = self.graph;
+        let mut stack = Stack::new();
+        self.marked[s] = true;
+        stack.push((s, graph.adj(s)));
+        while let Some(frame) = stack.peek_mut() {
+            let v = frame.0;
+            match frame.1.next() {
+                Some(&w) if !self.marked[w] => {
+                    self.marked[w] = true;
+                    self.edge_to[w] = Some(v);
+                    self.color[w] = !self.color[v];
+                    stack.push((w, graph.adj(w)));
+                }
+                Some(&w) if self.color[w] == self.color[v] => {
+                    let mut cycle = Stack::new();
+                    cycle.push(w);
+                    let mut x = v;
+                    while x != w {
+                        cycle.push(x);
+                        x = self.edge_to[x].unwrap();
+                    }
+                    cycle.push(w);
+                    self.odd_cycle = Some(cycle.into_iter().collect());
+                    return;
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
 }
 
 #[test]
@@ -99,3 +624,289 @@ fn test_graph() {
         assert!(vec![8, 4, 0].contains(w));
     }
 }
+
+#[cfg(test)]
+fn tiny_graph() -> Graph {
+    let mut g = Graph::new(13);
+    for &(v, w) in &[
+        (0, 5),
+        (4, 3),
+        (0, 1),
+        (9, 12),
+        (6, 4),
+        (5, 4),
+        (0, 2),
+        (11, 12),
+        (9, 10),
+        (0, 6),
+        (7, 8),
+        (9, 11),
+        (5, 3),
+    ] {
+        g.add_edge(v, w);
+    }
+    g
+}
+
+#[cfg(test)]
+fn is_cycle(g: &Graph, cycle: &[usize]) -> bool {
+    cycle.len() >= 2 && cycle.first() == cycle.last() && cycle.windows(2).all(|e| g.adj(e[0]).any(|&w| w == e[1]))
+}
+
+#[test]
+fn test_graph_search_paths() {
+    let g = tiny_graph();
+
+    let dfs = g.dfs(0);
+    assert!(dfs.has_path_to(3));
+    assert!(!dfs.has_path_to(7));
+    assert_eq!(None, dfs.path_to(9));
+    let path = dfs.path_to(4).unwrap();
+    assert_eq!(0, path[0]);
+    assert_eq!(4, *path.last().unwrap());
+
+    assert_eq!(Some(vec![0, 6, 4]), g.bfs(0).path_to(4));
+    assert_eq!(Some(vec![0]), g.bfs(0).path_to(0));
+
+    let multi = g.dfs_multi_source(vec![0, 7]);
+    assert!(multi.has_path_to(8));
+    assert!(!multi.has_path_to(10));
+}
+
+#[test]
+fn test_graph_connected_components() {
+    let g = tiny_graph();
+    let cc = g.connected_components();
+
+    assert_eq!(3, cc.count());
+    assert!(cc.connected(0, 3));
+    assert!(cc.connected(9, 12));
+    assert!(!cc.connected(6, 7));
+    assert_eq!(7, cc.size(4));
+    assert_eq!(2, cc.size(8));
+    assert_eq!(4, cc.size(11));
+    assert_eq!(cc.id(7), cc.id(8));
+}
+
+#[test]
+fn test_graph_cycle() {
+    let g = tiny_graph();
+    let cycle = g.cycle();
+    assert!(cycle.has_cycle());
+    assert!(is_cycle(&g, cycle.cycle().unwrap()));
+
+    let mut g = Graph::new(4);
+    g.add_edge(0, 1);
+    g.add_edge(1, 2);
+    g.add_edge(1, 3);
+    assert!(!g.cycle().has_cycle());
+    assert_eq!(None, g.cycle().cycle());
+
+    let mut parallel = g.clone();
+    parallel.add_edge(3, 1);
+    assert_eq!(3, parallel.cycle().cycle().unwrap().len());
+    assert!(is_cycle(&parallel, parallel.cycle().cycle().unwrap()));
+
+    let mut self_loop = g.clone();
+    self_loop.add_edge(2, 2);
+    assert_eq!(Some(&[2, 2][..]), self_loop.cycle().cycle());
+}
+
+#[test]
+fn test_graph_bipartition() {
+    let g = tiny_graph();
+    let odd_cycle = g.bipartition().err().unwrap();
+    assert!(is_cycle(&g, &odd_cycle));
+    assert_eq!(0, odd_cycle.len() % 2);
+
+    // an even cycle with a tail, plus an isolated vertex
+    let mut g = Graph::new(7);
+    for &(v, w) in &[(0, 1), (1, 2), (2, 3), (3, 0), (3, 4), (4, 5)] {
+        g.add_edge(v, w);
+    }
+    let (left, right) = g.bipartition().unwrap();
+    assert_eq!(7, left.len() + right.len());
+    for v in 0..g.vertices() {
+        for &w in g.adj(v) {
+            assert_ne!(left.contains(&v), left.contains(&w));
+        }
+    }
+}
+
+#[cfg(test)]
+fn count_components<I: IntoIterator<Item = (usize, usize)>>(n: usize, edges: I) -> usize {
+    let mut uf = UnionFind::new(n);
+    for (v, w) in edges {
+        uf.union(v, w);
+    }
+    uf.count()
+}
+
+#[test]
+fn test_graph_biconnectivity() {
+    let mut g = Graph::new(8);
+    // two triangles joined by the bridge 2 - 3, a pendant vertex 6 and an isolated vertex 7
+    for &(v, w) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6)] {
+        g.add_edge(v, w);
+    }
+    let bc = g.biconnectivity();
+
+    let mut bridges = bc
+        .bridges()
+        .iter()
+        .map(|&(v, w)| (v.min(w), v.max(w)))
+        .collect::<Vec<_>>();
+    bridges.sort();
+    assert_eq!(vec![(2, 3), (5, 6)], bridges);
+    assert_eq!(vec![2, 3, 5], bc.articulation_points());
+    assert!(!bc.is_articulation_point(7));
+
+    let mut sizes = bc.components().iter().map(|c| c.len()).collect::<Vec<_>>();
+    sizes.sort();
+    assert_eq!(vec![1, 1, 3, 3], sizes);
+
+    // a parallel edge is not a bridge
+    g.add_edge(3, 2);
+    assert_eq!(1, g.biconnectivity().bridges().len());
+}
+
+#[test]
+fn quicktest_graph_biconnectivity() {
+    fn prop(n: u8, edges: Vec<(u8, u8)>) -> bool {
+        let n = n as usize % 12 + 1;
+        let edges = edges
+            .into_iter()
+            .map(|(v, w)| (v as usize % n, w as usize % n))
+            .collect::<Vec<_>>();
+        let mut g = Graph::new(n);
+        for &(v, w) in &edges {
+            g.add_edge(v, w);
+        }
+        let bc = g.biconnectivity();
+        let count = count_components(n, edges.iter().cloned());
+
+        // a bridge is an edge whose removal increases the number of components
+        let mut bridges = bc
+            .bridges()
+            .iter()
+            .map(|&(v, w)| (v.min(w), v.max(w)))
+            .collect::<Vec<_>>();
+        bridges.sort();
+        let mut expected = (0..edges.len())
+            .filter(|&i| {
+                let rest = edges.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &e)| e);
+                count_components(n, rest) > count
+            })
+            .map(|i| (edges[i].0.min(edges[i].1), edges[i].0.max(edges[i].1)))
+            .collect::<Vec<_>>();
+        expected.sort();
+        if bridges != expected {
+            return false;
+        }
+
+        // an articulation point is a vertex whose removal increases the number of components
+        let articulation = (0..n)
+            .filter(|&x| {
+                let rest = edges.iter().cloned().filter(|&(v, w)| v != x && w != x);
+                count_components(n, rest) - 1 > count
+            })
+            .collect::<Vec<_>>();
+        if bc.articulation_points() != articulation {
+            return false;
+        }
+
+        // every edge is in exactly one biconnected component, and no component has an articulation point of its own
+        let total: usize = bc.components().iter().map(|c| c.len()).sum();
+        total == edges.len() &&
+            bc.components().iter().all(|c| {
+                let mut vertices = c.iter().flat_map(|&(v, w)| vec![v, w]).collect::<Vec<_>>();
+                vertices.sort();
+                vertices.dedup();
+                // a self-loop component has a single vertex
+                vertices.len() == 1 ||
+                    vertices.iter().all(|&x| {
+                        // the remaining vertices of the component stay in one piece
+                        let rest = c.iter().cloned().filter(|&(v, w)| v != x && w != x);
+                        count_components(n, rest) == n - vertices.len() + 2
+                    })
+            })
+    }
+
+    quickcheck(prop as fn(u8, Vec<(u8, u8)>) -> bool);
+}
+
+#[test]
+fn test_graph_mutation() {
+    let mut g = tiny_graph();
+    assert!(g.has_edge(0, 5) && g.has_edge(5, 0));
+    assert!(g.remove_edge(5, 0));
+    assert!(!g.has_edge(0, 5) && !g.remove_edge(0, 5));
+    assert_eq!((12, 3), (g.edges(), g.degree(0)));
+
+    let v = g.add_vertex();
+    assert_eq!((13, 14), (v, g.vertices()));
+    g.add_edge(v, v);
+    g.add_edge(v, 0);
+    assert_eq!(3, g.degree(v));
+    assert!(g.remove_edge(v, v));
+    assert_eq!(1, g.degree(v));
+
+    // the last vertex takes the place of 0
+    g.remove_vertex(0);
+    assert_eq!((13, 9), (g.vertices(), g.edges()));
+    assert_eq!(0, g.degree(0));
+    assert_eq!(vec![(3, 4), (3, 5), (4, 5), (4, 6)], {
+        let mut edges = g.all_edges();
+        edges.retain(|&(v, w)| v < 7 && w < 7);
+        edges.sort();
+        edges
+    });
+}
+
+#[test]
+fn quicktest_graph_remove_vertex() {
+    fn prop(n: u8, x: u8, edges: Vec<(u8, u8)>) -> bool {
+        let n = n as usize % 12 + 1;
+        let x = x as usize % n;
+        let edges = edges
+            .into_iter()
+            .map(|(v, w)| (v as usize % n, w as usize % n))
+            .collect::<Vec<_>>();
+        let mut g = Graph::new(n);
+        for &(v, w) in &edges {
+            g.add_edge(v, w);
+        }
+        g.remove_vertex(x);
+
+        let renumber = |v: usize| if v == n - 1 { x } else { v };
+        let mut expected = Graph::new(n - 1);
+        for &(v, w) in edges.iter().filter(|&&(v, w)| v != x && w != x) {
+            expected.add_edge(renumber(v), renumber(w));
+        }
+        g.edges() == expected.edges() && g.adjacency_matrix() == expected.adjacency_matrix()
+    }
+
+    quickcheck(prop as fn(u8, u8, Vec<(u8, u8)>) -> bool);
+}
+
+#[test]
+fn test_graph_long_path() {
+    let n = 1_000_000;
+    let mut g = Graph::new(n);
+    for v in 1..n {
+        g.add_edge(v - 1, v);
+    }
+
+    assert_eq!(Some(n), g.dfs(0).path_to(n - 1).map(|path| path.len()));
+    assert_eq!(1, g.connected_components().count());
+    assert!(!g.cycle().has_cycle());
+    assert!(g.bipartition().is_ok());
+    let bc = g.biconnectivity();
+    assert_eq!(n - 1, bc.bridges().len());
+    assert_eq!(n - 2, bc.articulation_points().len());
+
+    g.add_edge(n - 1, 0);
+    assert_eq!(Some(n + 1), g.cycle().cycle().map(|c| c.len()));
+    assert_eq!(0, g.biconnectivity().bridges().len());
+    assert_eq!(0, g.biconnectivity().articulation_points().len());
+}
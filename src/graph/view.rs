@@ -0,0 +1,16 @@
+/// Read access to a graph whose vertices are numbered from `0` to `vertex_count() - 1`, enough for searches to
+/// run on any representation.
+///
+/// An undirected graph lists each edge in the adjacency of both endpoints.
+pub trait GraphView {
+    type Neighbors<'a>: Iterator<Item = usize>
+    where
+        Self: 'a;
+
+    fn vertex_count(&self) -> usize;
+
+    fn edge_count(&self) -> usize;
+
+    /// Vertices adjacent to `v`, or pointed to from `v` in a digraph.
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_>;
+}
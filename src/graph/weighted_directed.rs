@@ -0,0 +1,434 @@
+use super::super::bag;
+use super::super::bag::Bag;
+use super::super::priority_queue::IndexMinPQ;
+use super::super::stack::Stack;
+use super::all_pairs;
+use super::all_pairs::AllPairsShortestPaths;
+use super::directed::Digraph;
+use std::f64;
+use std::iter;
+
+/// A weighted edge directed from `from` to `to`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectedEdge {
+    from: usize,
+    to: usize,
+    weight: f64,
+}
+
+impl DirectedEdge {
+    pub fn new(from: usize, to: usize, weight: f64) -> DirectedEdge {
+        assert!(!weight.is_nan(), "weight is NaN");
+        DirectedEdge {
+            from: from,
+            to: to,
+            weight: weight,
+        }
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeWeightedDigraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Bag<DirectedEdge>>,
+}
+
+impl EdgeWeightedDigraph {
+    pub fn new(v: usize) -> EdgeWeightedDigraph {
+        EdgeWeightedDigraph {
+            v: v,
+            e: 0,
+            adj: iter::repeat(Bag::<DirectedEdge>::new()).take(v).collect(),
+        }
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(v < self.v, "vertex is not between 0 and {}", self.v - 1)
+    }
+
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    pub fn add_edge(&mut self, e: DirectedEdge) {
+        self.validate_vertex(e.from());
+        self.validate_vertex(e.to());
+
+        self.e += 1;
+        self.adj[e.from()].add(e);
+    }
+
+    pub fn outdegree(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.adj[v].len()
+    }
+
+    pub fn adj(&self, v: usize) -> bag::Iter<DirectedEdge> {
+        self.adj[v].iter()
+    }
+
+    pub fn all_edges(&self) -> Vec<DirectedEdge> {
+        self.adj.iter().flat_map(|adj| adj.iter().cloned()).collect()
+    }
+
+    /// The underlying unweighted digraph.
+    pub fn to_digraph(&self) -> Digraph {
+        let mut g = Digraph::new(self.v);
+        for e in self.all_edges() {
+            g.add_edge(e.from(), e.to());
+        }
+        g
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        dot.push_str("digraph G {\n");
+        for i in 0..self.v {
+            dot.push_str(&format!("  {};\n", i));
+        }
+
+        for e in self.all_edges() {
+            dot.push_str(&format!("  {} -> {} [label=\"{}\"];\n", e.from(), e.to(), e.weight()));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Single-source shortest paths by Dijkstra's algorithm. All edge weights must be nonnegative.
+    pub fn dijkstra(&self, s: usize) -> ShortestPaths {
+        self.validate_vertex(s);
+        let mut sp = ShortestPaths::new(self.v, s, f64::INFINITY);
+        let mut pq = IndexMinPQ::with_capacity(self.v);
+        pq.insert(s, 0.0);
+        while let Some(v) = pq.del_min() {
+            for e in self.adj(v) {
+                assert!(e.weight() >= 0.0, "edge {:?} has negative weight", e);
+                if sp.relax(e) {
+                    let w = e.to();
+                    if pq.contains(w) {
+                        pq.decrease_key(w, sp.dist_to[w]);
+                    } else {
+                        pq.insert(w, sp.dist_to[w]);
+                    }
+                }
+            }
+        }
+        sp
+    }
+
+    /// Single-source shortest paths by the Bellman-Ford algorithm. Edge weights may be negative.
+    ///
+    /// Returns the edges of a negative cycle reachable from `s` as the error, if there is one.
+    pub fn bellman_ford(&self, s: usize) -> Result<ShortestPaths, Vec<DirectedEdge>> {
+        self.validate_vertex(s);
+        let mut sp = ShortestPaths::new(self.v, s, f64::INFINITY);
+        let edges = self.all_edges();
+
+        // after V-1 passes every shortest path is settled, a V-th pass that still relaxes reveals a negative cycle
+        let mut relaxed = None;
+        for _ in 0..self.v {
+            relaxed = None;
+            for e in &edges {
+                if sp.dist_to[e.from()] < f64::INFINITY && sp.relax(e) {
+                    relaxed = Some(e.to());
+                }
+            }
+            if relaxed.is_none() {
+                return Ok(sp);
+            }
+        }
+
+        // the last relaxed vertex might hang off the cycle, walking back V edges is sure to land on it
+        let mut x = relaxed.unwrap();
+        for _ in 0..self.v {
+            x = sp.edge_to[x].unwrap().from();
+        }
+
+        let mut cycle = Stack::new();
+        let mut y = x;
+        loop {
+            let e = sp.edge_to[y].unwrap();
+            cycle.push(e);
+            y = e.from();
+            if y == x {
+                break;
+            }
+        }
+        Err(cycle.into_iter().collect())
+    }
+
+    /// Single-source shortest paths in an edge-weighted DAG, relaxing vertices in topological order.
+    ///
+    /// Returns `None` if the digraph has a directed cycle.
+    pub fn acyclic_sp(&self, s: usize) -> Option<ShortestPaths> {
+        self.acyclic_paths(s, false)
+    }
+
+    /// Single-source longest paths in an edge-weighted DAG.
+    ///
+    /// Returns `None` if the digraph has a directed cycle.
+    pub fn acyclic_lp(&self, s: usize) -> Option<ShortestPaths> {
+        self.acyclic_paths(s, true)
+    }
+
+    /// Shortest paths between all pairs of vertices by the Floyd-Warshall algorithm, for small dense digraphs.
+    ///
+    /// Returns the edges of a negative cycle as the error, if there is one.
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths, Vec<DirectedEdge>> {
+        all_pairs::floyd_warshall(self)
+    }
+
+    /// Shortest paths between all pairs of vertices by Johnson's algorithm, for large sparse digraphs.
+    ///
+    /// Returns the edges of a negative cycle as the error, if there is one.
+    pub fn johnson(&self) -> Result<AllPairsShortestPaths, Vec<DirectedEdge>> {
+        all_pairs::johnson(self)
+    }
+
+    fn acyclic_paths(&self, s: usize, longest: bool) -> Option<ShortestPaths> {
+        self.validate_vertex(s);
+        let order = self.to_digraph().topological_order().ok()?;
+        let mut sp = ShortestPaths::new(self.v, s, if longest { f64::NEG_INFINITY } else { f64::INFINITY });
+        for &v in order.order() {
+            if sp.edge_to[v].is_none() && v != s {
+                continue;
+            }
+            for e in self.adj(v) {
+                if longest {
+                    sp.relax_longest(e);
+                } else {
+                    sp.relax(e);
+                }
+            }
+        }
+        Some(sp)
+    }
+}
+
+/// Result of a single-source weighted paths computation: a tree of paths rooted at the source.
+pub struct ShortestPaths {
+    source: usize,
+    dist_to: Vec<f64>,
+    edge_to: Vec<Option<DirectedEdge>>,
+}
+
+impl ShortestPaths {
+    fn new(n: usize, source: usize, unreached: f64) -> ShortestPaths {
+        let mut dist_to = vec![unreached; n];
+        dist_to[source] = 0.0;
+        ShortestPaths {
+            source: source,
+            dist_to: dist_to,
+            edge_to: vec![None; n],
+        }
+    }
+
+    fn relax(&mut self, e: &DirectedEdge) -> bool {
+        let (v, w) = (e.from(), e.to());
+        if self.dist_to[w] > self.dist_to[v] + e.weight() {
+            self.dist_to[w] = self.dist_to[v] + e.weight();
+            self.edge_to[w] = Some(*e);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn relax_longest(&mut self, e: &DirectedEdge) -> bool {
+        let (v, w) = (e.from(), e.to());
+        if self.dist_to[w] < self.dist_to[v] + e.weight() {
+            self.dist_to[w] = self.dist_to[v] + e.weight();
+            self.edge_to[w] = Some(*e);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Length of the path from the source to `v`. Without one it is `+inf` for shortest paths, `-inf` for longest.
+    pub fn dist_to(&self, v: usize) -> f64 {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        v == self.source || self.edge_to[v].is_some()
+    }
+
+    pub fn path_to(&self, v: usize) -> Option<Vec<DirectedEdge>> {
+        if self.has_path_to(v) {
+            let mut path = Stack::new();
+            let mut x = v;
+            while let Some(e) = self.edge_to[x] {
+                path.push(e);
+                x = e.from();
+            }
+            Some(path.into_iter().collect())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+fn digraph_from_edges(v: usize, edges: &[(usize, usize, f64)]) -> EdgeWeightedDigraph {
+    let mut g = EdgeWeightedDigraph::new(v);
+    for &(v, w, weight) in edges {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+    g
+}
+
+#[cfg(test)]
+fn assert_dists(sp: &ShortestPaths, expected: &[f64]) {
+    for (v, &d) in expected.iter().enumerate() {
+        assert!((sp.dist_to(v) - d).abs() < 1e-9, "dist_to({}) = {}, expected {}", v, sp.dist_to(v), d);
+        let len: f64 = sp.path_to(v).unwrap().iter().map(|e| e.weight()).sum();
+        assert!((len - d).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_edge_weighted_digraph() {
+    let g = digraph_from_edges(4, &[(0, 1, 0.5), (1, 2, 0.25), (2, 0, 1.0), (2, 3, 2.0)]);
+    assert_eq!(4, g.v());
+    assert_eq!(4, g.e());
+    assert_eq!(2, g.outdegree(2));
+    assert_eq!(4, g.all_edges().len());
+    assert_eq!(4, g.to_digraph().e());
+
+    for e in g.adj(2) {
+        assert_eq!(2, e.from());
+        assert!(vec![0, 3].contains(&e.to()));
+    }
+}
+
+#[test]
+fn test_dijkstra_shortest_paths() {
+    let g = digraph_from_edges(
+        8,
+        &[
+            (4, 5, 0.35),
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ],
+    );
+
+    let sp = g.dijkstra(0);
+    assert_dists(&sp, &[0.0, 1.05, 0.26, 0.99, 0.38, 0.73, 1.51, 0.60]);
+
+    let path = sp.path_to(6).unwrap().iter().map(|e| e.to()).collect::<Vec<usize>>();
+    assert_eq!(vec![2, 7, 3, 6], path);
+    assert_eq!(Some(vec![]), sp.path_to(0));
+
+    let sp = g.dijkstra(1);
+    assert!(sp.has_path_to(0));
+    assert_dists(&sp, &[1.39, 0.0, 1.21, 0.29, 1.74, 1.83, 0.81, 1.55]);
+}
+
+#[test]
+fn test_bellman_ford_shortest_paths() {
+    let mut edges = vec![
+        (4, 5, 0.35),
+        (5, 4, 0.35),
+        (4, 7, 0.37),
+        (5, 7, 0.28),
+        (7, 5, 0.28),
+        (5, 1, 0.32),
+        (0, 4, 0.38),
+        (0, 2, 0.26),
+        (7, 3, 0.39),
+        (1, 3, 0.29),
+        (2, 7, 0.34),
+        (6, 2, -1.20),
+        (3, 6, 0.52),
+        (6, 0, -1.40),
+        (6, 4, -1.25),
+    ];
+    let g = digraph_from_edges(9, &edges);
+    let sp = g.bellman_ford(0).unwrap();
+    assert_dists(&sp, &[0.0, 0.93, 0.26, 0.99, 0.26, 0.61, 1.51, 0.60]);
+    assert!(!sp.has_path_to(8));
+    assert_eq!(None, sp.path_to(8));
+    assert_eq!(f64::INFINITY, sp.dist_to(8));
+
+    // 4 -> 5 -> 4 is a negative cycle
+    edges[1] = (5, 4, -0.66);
+    edges[11] = (6, 2, 0.40);
+    edges[13] = (6, 0, 0.58);
+    edges[14] = (6, 4, 0.93);
+    let g = digraph_from_edges(9, &edges);
+    let cycle = g.bellman_ford(0).err().unwrap();
+    assert_eq!(2, cycle.len());
+    assert!(cycle.iter().map(|e| e.weight()).sum::<f64>() < 0.0);
+    assert_eq!(cycle[0].to(), cycle[1].from());
+    assert_eq!(cycle[1].to(), cycle[0].from());
+
+    // the cycle is not reachable from 8
+    assert!(g.bellman_ford(8).is_ok());
+}
+
+#[test]
+fn test_acyclic_paths() {
+    let mut g = digraph_from_edges(
+        8,
+        &[
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (5, 1, 0.32),
+            (4, 0, 0.38),
+            (0, 2, 0.26),
+            (3, 7, 0.39),
+            (1, 3, 0.29),
+            (7, 2, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ],
+    );
+
+    let sp = g.acyclic_sp(5).unwrap();
+    assert_dists(&sp, &[0.73, 0.32, 0.62, 0.61, 0.35, 0.0, 1.13, 0.28]);
+
+    let lp = g.acyclic_lp(5).unwrap();
+    assert_dists(&lp, &[2.44, 0.32, 2.77, 0.61, 2.06, 0.0, 1.13, 2.43]);
+
+    let sp = g.acyclic_sp(1).unwrap();
+    assert!(!sp.has_path_to(5));
+    assert!(!g.acyclic_lp(1).unwrap().has_path_to(5));
+
+    g.add_edge(DirectedEdge::new(2, 5, 1.0));
+    assert!(g.acyclic_sp(5).is_none());
+    assert!(g.acyclic_lp(5).is_none());
+}
@@ -0,0 +1,345 @@
+use super::super::bag;
+use super::super::bag::Bag;
+use super::super::priority_queue::{IndexMinPQ, MinPQ};
+use super::super::queue::{self, Queue};
+use super::super::union_find::UnionFind;
+use std::cmp::Ordering;
+use std::f64;
+use std::iter;
+
+/// A weighted undirected edge between `v` and `w`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Edge {
+    v: usize,
+    w: usize,
+    weight: f64,
+}
+
+impl Edge {
+    pub fn new(v: usize, w: usize, weight: f64) -> Edge {
+        assert!(!weight.is_nan(), "weight is NaN");
+        Edge {
+            v: v,
+            w: w,
+            weight: weight,
+        }
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Either endpoint of the edge.
+    pub fn either(&self) -> usize {
+        self.v
+    }
+
+    /// The endpoint of the edge that is different from `vertex`.
+    pub fn other(&self, vertex: usize) -> usize {
+        if vertex == self.v {
+            self.w
+        } else if vertex == self.w {
+            self.v
+        } else {
+            panic!("illegal endpoint")
+        }
+    }
+}
+
+/// Edges are ordered by their weights.
+impl PartialOrd for Edge {
+    fn partial_cmp(&self, other: &Edge) -> Option<Ordering> {
+        self.weight.partial_cmp(&other.weight)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeWeightedGraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Bag<Edge>>,
+}
+
+impl EdgeWeightedGraph {
+    pub fn new(v: usize) -> EdgeWeightedGraph {
+        EdgeWeightedGraph {
+            v: v,
+            e: 0,
+            adj: iter::repeat(Bag::<Edge>::new()).take(v).collect(),
+        }
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(v < self.v, "vertex is not between 0 and {}", self.v - 1)
+    }
+
+    pub fn vertices(&self) -> usize {
+        self.v
+    }
+
+    pub fn edges(&self) -> usize {
+        self.e
+    }
+
+    pub fn add_edge(&mut self, e: Edge) {
+        let v = e.either();
+        let w = e.other(v);
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+
+        self.e += 1;
+        self.adj[v].add(e);
+        self.adj[w].add(e);
+    }
+
+    pub fn degree(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.adj[v].len()
+    }
+
+    pub fn adj(&self, v: usize) -> bag::Iter<Edge> {
+        self.adj[v].iter()
+    }
+
+    /// All edges of the graph, each one reported once.
+    pub fn all_edges(&self) -> Vec<Edge> {
+        let mut edges = Vec::with_capacity(self.e);
+        for v in 0..self.v {
+            let mut self_loops = 0;
+            for e in self.adj(v) {
+                let w = e.other(v);
+                if w > v {
+                    edges.push(*e);
+                } else if w == v {
+                    // a self-loop appears twice in adj[v]
+                    if self_loops % 2 == 0 {
+                        edges.push(*e);
+                    }
+                    self_loops += 1;
+                }
+            }
+        }
+        edges
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        dot.push_str("graph G {\n");
+        for i in 0..self.v {
+            dot.push_str(&format!("  {};\n", i));
+        }
+
+        for e in self.all_edges() {
+            let v = e.either();
+            dot.push_str(&format!("  {} -- {} [label=\"{}\"];\n", v, e.other(v), e.weight()));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn kruskal_mst(&self) -> MST {
+        MST::kruskal(self)
+    }
+
+    pub fn lazy_prim_mst(&self) -> MST {
+        MST::lazy_prim(self)
+    }
+
+    pub fn prim_mst(&self) -> MST {
+        MST::prim(self)
+    }
+}
+
+/// A minimum spanning tree (or forest, when the graph is not connected).
+pub struct MST {
+    edges: Queue<Edge>,
+    weight: f64,
+}
+
+impl MST {
+    fn from_edges(edges: Queue<Edge>) -> MST {
+        let weight = edges.iter().map(|e| e.weight()).sum();
+        MST {
+            edges: edges,
+            weight: weight,
+        }
+    }
+
+    /// Kruskal's algorithm: add edges in ascending order of weight, skipping those that would form a cycle.
+    fn kruskal(graph: &EdgeWeightedGraph) -> MST {
+        let n = graph.vertices();
+        let mut mst = Queue::new();
+        let mut pq = MinPQ::from_vec(graph.all_edges());
+        let mut uf = UnionFind::new(n);
+
+        let mut count = 0;
+        while !pq.is_empty() && count + 1 < n {
+            let e = pq.del_min().unwrap();
+            let v = e.either();
+            let w = e.other(v);
+            if !uf.connected(v, w) {
+                uf.union(v, w);
+                mst.enqueue(e);
+                count += 1;
+            }
+        }
+        MST::from_edges(mst)
+    }
+
+    /// Lazy Prim's algorithm: obsolete crossing edges are left on the priority queue.
+    fn lazy_prim(graph: &EdgeWeightedGraph) -> MST {
+        fn visit(graph: &EdgeWeightedGraph, marked: &mut [bool], pq: &mut MinPQ<Edge>, v: usize) {
+            marked[v] = true;
+            for e in graph.adj(v) {
+                if !marked[e.other(v)] {
+                    pq.insert(*e);
+                }
+            }
+        }
+
+        let mut marked = vec![false; graph.vertices()];
+        let mut mst = Queue::new();
+        let mut pq = MinPQ::new();
+        for s in 0..graph.vertices() {
+            if marked[s] {
+                continue;
+            }
+            visit(graph, &mut marked, &mut pq, s);
+            while !pq.is_empty() {
+                let e = pq.del_min().unwrap();
+                let v = e.either();
+                let w = e.other(v);
+                if marked[v] && marked[w] {
+                    continue;
+                }
+                mst.enqueue(e);
+                if !marked[v] {
+                    visit(graph, &mut marked, &mut pq, v);
+                }
+                if !marked[w] {
+                    visit(graph, &mut marked, &mut pq, w);
+                }
+            }
+        }
+        MST::from_edges(mst)
+    }
+
+    /// Eager Prim's algorithm: keep only the lightest crossing edge to each vertex in an indexed priority queue.
+    fn prim(graph: &EdgeWeightedGraph) -> MST {
+        let n = graph.vertices();
+        let mut edge_to: Vec<Option<Edge>> = vec![None; n];
+        let mut dist_to = vec![f64::INFINITY; n];
+        let mut marked = vec![false; n];
+        let mut pq = IndexMinPQ::with_capacity(n);
+
+        for s in 0..n {
+            if marked[s] {
+                continue;
+            }
+            dist_to[s] = 0.0;
+            pq.insert(s, 0.0);
+            while let Some(v) = pq.del_min() {
+                marked[v] = true;
+                for e in graph.adj(v) {
+                    let w = e.other(v);
+                    if marked[w] || e.weight() >= dist_to[w] {
+                        continue;
+                    }
+                    dist_to[w] = e.weight();
+                    edge_to[w] = Some(*e);
+                    if pq.contains(w) {
+                        pq.decrease_key(w, dist_to[w]);
+                    } else {
+                        pq.insert(w, dist_to[w]);
+                    }
+                }
+            }
+        }
+
+        let mut mst = Queue::new();
+        for e in edge_to.into_iter().flatten() {
+            mst.enqueue(e);
+        }
+        MST::from_edges(mst)
+    }
+
+    pub fn edges(&self) -> queue::Iter<Edge> {
+        self.edges.iter()
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+#[cfg(test)]
+fn tiny_ewg() -> EdgeWeightedGraph {
+    let mut g = EdgeWeightedGraph::new(8);
+    for &(v, w, weight) in &[
+        (4, 5, 0.35),
+        (4, 7, 0.37),
+        (5, 7, 0.28),
+        (0, 7, 0.16),
+        (1, 5, 0.32),
+        (0, 4, 0.38),
+        (2, 3, 0.17),
+        (1, 7, 0.19),
+        (0, 2, 0.26),
+        (1, 2, 0.36),
+        (1, 3, 0.29),
+        (2, 7, 0.34),
+        (6, 2, 0.40),
+        (3, 6, 0.52),
+        (6, 0, 0.58),
+        (6, 4, 0.93),
+    ] {
+        g.add_edge(Edge::new(v, w, weight));
+    }
+    g
+}
+
+#[test]
+fn test_edge_weighted_graph() {
+    let mut g = tiny_ewg();
+    assert_eq!(8, g.vertices());
+    assert_eq!(16, g.edges());
+    assert_eq!(4, g.degree(0));
+    assert_eq!(16, g.all_edges().len());
+
+    g.add_edge(Edge::new(3, 3, 1.0));
+    assert_eq!(17, g.all_edges().len());
+    assert_eq!(5, g.degree(3));
+
+    for e in g.adj(5) {
+        assert!(vec![4, 7, 1].contains(&e.other(5)));
+    }
+}
+
+#[test]
+fn test_minimum_spanning_tree() {
+    let g = tiny_ewg();
+
+    for mst in vec![g.kruskal_mst(), g.lazy_prim_mst(), g.prim_mst()] {
+        assert_eq!(7, mst.edges().count());
+        assert!((mst.weight() - 1.81).abs() < 1e-9);
+
+        let mut weights = mst.edges().map(|e| e.weight()).collect::<Vec<f64>>();
+        weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![0.16, 0.17, 0.19, 0.26, 0.28, 0.35, 0.40], weights);
+    }
+}
+
+#[test]
+fn test_minimum_spanning_forest() {
+    let mut g = EdgeWeightedGraph::new(5);
+    g.add_edge(Edge::new(0, 1, 1.0));
+    g.add_edge(Edge::new(1, 2, 2.0));
+    g.add_edge(Edge::new(0, 2, 0.5));
+    g.add_edge(Edge::new(3, 4, 3.0));
+
+    for mst in vec![g.kruskal_mst(), g.lazy_prim_mst(), g.prim_mst()] {
+        assert_eq!(3, mst.edges().count());
+        assert_eq!(4.5, mst.weight());
+    }
+}
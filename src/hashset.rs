@@ -0,0 +1,386 @@
+use super::hashst::{self, HashST};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Chain, FromIterator};
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+#[cfg(test)]
+use std::collections::HashSet;
+
+/// A set of keys, a `HashST` mapping each key to `()`.
+pub struct HashSET<K, S = RandomState> {
+    st: HashST<K, (), S>,
+}
+
+impl<K: Hash + PartialEq> HashSET<K, RandomState> {
+    pub fn new() -> HashSET<K, RandomState> {
+        HashSET { st: HashST::new() }
+    }
+
+    /// An empty set with room for `capacity` keys before it has to grow.
+    pub fn with_capacity(capacity: usize) -> HashSET<K, RandomState> {
+        HashSET {
+            st: HashST::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> HashSET<K, S> {
+    pub fn with_hasher(hash_builder: S) -> HashSET<K, S> {
+        HashSET {
+            st: HashST::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HashSET<K, S> {
+        HashSET {
+            st: HashST::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.st.hasher()
+    }
+
+    /// Number of keys the set holds before it has to grow.
+    pub fn capacity(&self) -> usize {
+        self.st.capacity()
+    }
+
+    pub fn contains<T: Borrow<K>>(&self, key: T) -> bool {
+        self.st.contains_key(key)
+    }
+
+    /// Add `key` to the set, returning whether it was new.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.st.insert(key, ()).is_none()
+    }
+
+    /// Take `key` out of the set, returning whether it was there.
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.st.remove(key).is_some()
+    }
+
+    pub fn size(&self) -> usize {
+        self.st.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.st.is_empty()
+    }
+
+    /// Keys in either set, each once.
+    pub fn union<'a>(&'a self, other: &'a HashSET<K, S>) -> Union<'a, K, S> {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Keys in both sets, found by walking the smaller one.
+    pub fn intersection<'a>(&'a self, other: &'a HashSET<K, S>) -> Intersection<'a, K, S> {
+        let (small, large) = if self.size() <= other.size() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Intersection {
+            iter: small.iter(),
+            other: large,
+        }
+    }
+
+    /// Keys in `self` but not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a HashSET<K, S>) -> Difference<'a, K, S> {
+        Difference {
+            iter: self.iter(),
+            other: other,
+        }
+    }
+
+    /// Keys in exactly one of the sets.
+    pub fn symmetric_difference<'a>(&'a self, other: &'a HashSET<K, S>) -> SymmetricDifference<'a, K, S> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    /// Whether every key in `self` is in `other`.
+    pub fn is_subset(&self, other: &HashSET<K, S>) -> bool {
+        self.size() <= other.size() && self.iter().all(|k| other.contains(k))
+    }
+}
+
+impl<K, S> HashSET<K, S> {
+    /// Iterate over the keys, in no particular order.
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter { inner: self.st.keys() }
+    }
+
+    /// Take every key out of the set. The set keeps its buckets.
+    pub fn drain(&mut self) -> IntoIter<K> {
+        IntoIter { inner: self.st.drain() }
+    }
+
+    /// Remove every key. The set keeps its buckets.
+    pub fn clear(&mut self) {
+        self.st.clear();
+    }
+}
+
+pub struct Iter<'a, K>
+where
+    K: 'a,
+{
+    inner: hashst::Keys<'a, K, ()>,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K> ExactSizeIterator for Iter<'a, K> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+pub struct IntoIter<K> {
+    inner: hashst::IntoIter<K, ()>,
+}
+
+impl<K> Iterator for IntoIter<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K> ExactSizeIterator for IntoIter<K> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+pub struct Difference<'a, K, S>
+where
+    K: 'a,
+    S: 'a,
+{
+    iter: Iter<'a, K>,
+    other: &'a HashSET<K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Difference<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        let other = self.other;
+        self.iter.find(|&k| !other.contains(k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct Intersection<'a, K, S>
+where
+    K: 'a,
+    S: 'a,
+{
+    iter: Iter<'a, K>,
+    other: &'a HashSET<K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Intersection<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        let other = self.other;
+        self.iter.find(|&k| other.contains(k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct Union<'a, K, S>
+where
+    K: 'a,
+    S: 'a,
+{
+    iter: Chain<Iter<'a, K>, Difference<'a, K, S>>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Union<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct SymmetricDifference<'a, K, S>
+where
+    K: 'a,
+    S: 'a,
+{
+    iter: Chain<Difference<'a, K, S>, Difference<'a, K, S>>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for SymmetricDifference<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, S> IntoIterator for HashSET<K, S> {
+    type Item = K;
+    type IntoIter = IntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.st.into_iter(),
+        }
+    }
+}
+
+impl<'a, K, S> IntoIterator for &'a HashSET<K, S> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher + Default> Default for HashSET<K, S> {
+    fn default() -> Self {
+        HashSET { st: HashST::default() }
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher + Default> FromIterator<K> for HashSET<K, S> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = HashSET::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> Extend<K> for HashSET<K, S> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.st.extend(iter.into_iter().map(|k| (k, ())));
+    }
+}
+
+impl<K: fmt::Debug, S> fmt::Debug for HashSET<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Clone, S: Clone> Clone for HashSET<K, S> {
+    fn clone(&self) -> Self {
+        HashSET { st: self.st.clone() }
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> PartialEq for HashSET<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.st == other.st
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Eq for HashSET<K, S> {}
+
+#[cfg(test)]
+fn sorted<'a, I: Iterator<Item = &'a u32>>(iter: I) -> Vec<u32> {
+    let mut keys = iter.cloned().collect::<Vec<u32>>();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_hash_set() {
+    let mut s = HashSET::new();
+    assert!(s.is_empty());
+    assert!(s.insert("apple"));
+    assert!(s.insert("pear"));
+    assert!(!s.insert("apple"));
+    assert_eq!(2, s.size());
+    assert!(s.contains("apple") && !s.contains("plum"));
+    assert!(s.remove(&"apple"));
+    assert!(!s.remove(&"apple"));
+    assert_eq!(vec!["pear"], s.iter().cloned().collect::<Vec<&str>>());
+    assert_eq!("{\"pear\"}", format!("{:?}", s));
+
+    let words = "to be or not to be".split(' ').collect::<HashSET<&str>>();
+    assert_eq!(4, words.size());
+    let mut copy = words.clone();
+    assert!(copy == words);
+    copy.extend(vec!["that", "is"]);
+    assert!(copy != words && words.is_subset(&copy) && !copy.is_subset(&words));
+    let mut drained = copy.drain().collect::<Vec<&str>>();
+    drained.sort();
+    assert_eq!(vec!["be", "is", "not", "or", "that", "to"], drained);
+    assert!(copy.is_empty());
+}
+
+#[test]
+fn test_hash_set_algebra() {
+    let a = (0..10).collect::<HashSET<u32>>();
+    let b = (5..15).collect::<HashSET<u32>>();
+    assert_eq!((0..15).collect::<Vec<u32>>(), sorted(a.union(&b)));
+    assert_eq!((5..10).collect::<Vec<u32>>(), sorted(a.intersection(&b)));
+    assert_eq!((0..5).collect::<Vec<u32>>(), sorted(a.difference(&b)));
+    assert_eq!((10..15).collect::<Vec<u32>>(), sorted(b.difference(&a)));
+    assert_eq!(vec![0, 1, 2, 3, 4, 10, 11, 12, 13, 14], sorted(a.symmetric_difference(&b)));
+
+    let empty = HashSET::new();
+    assert!(empty.is_subset(&a) && !a.is_subset(&empty));
+    assert_eq!(0, a.intersection(&empty).count());
+    assert_eq!(10, a.union(&empty).count());
+}
+
+#[test]
+fn quicktest_hash_set_algebra() {
+    fn prop(a: Vec<u8>, b: Vec<u8>) -> bool {
+        let (a, b) = (a.into_iter().map(u32::from), b.into_iter().map(u32::from));
+        let (sa, sb) = (a.clone().collect::<HashSET<u32>>(), b.clone().collect::<HashSET<u32>>());
+        let (ea, eb) = (a.collect::<HashSet<u32>>(), b.collect::<HashSet<u32>>());
+        sorted(sa.union(&sb)) == sorted(ea.union(&eb)) &&
+            sorted(sa.intersection(&sb)) == sorted(ea.intersection(&eb)) &&
+            sorted(sa.difference(&sb)) == sorted(ea.difference(&eb)) &&
+            sorted(sa.symmetric_difference(&sb)) == sorted(ea.symmetric_difference(&eb)) &&
+            sa.is_subset(&sb) == ea.is_subset(&eb)
+    }
+    quickcheck(prop as fn(Vec<u8>, Vec<u8>) -> bool);
+}
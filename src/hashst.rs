@@ -1,7 +1,29 @@
 use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Index, IndexMut};
+use std::slice;
+use std::vec;
+
+#[cfg(test)]
+use quickcheck::quickcheck;
+#[cfg(test)]
+use std::cell::Cell;
+#[cfg(test)]
 use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-use std::ops::Index;
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use std::hash::{BuildHasherDefault, Hasher};
+#[cfg(test)]
+use std::rc::Rc;
+
+pub mod linear_probing;
+
+pub use self::linear_probing::LinearProbingHashST;
 
 struct Node<K, V> {
     key: K,
@@ -9,53 +31,162 @@ struct Node<K, V> {
     next: Option<Box<Node<K, V>>>,
 }
 
-impl<K, V> Node<K, V> {
-    pub fn size(&self) -> usize {
-        match self.next {
-            None => 1,
-            Some(ref next) => 1 + next.size(),
+impl<K: Clone, V: Clone> Clone for Node<K, V> {
+    fn clone(&self) -> Self {
+        Node {
+            key: self.key.clone(),
+            val: self.val.clone(),
+            next: self.next.clone(),
         }
     }
 }
 
-fn delete<K: PartialEq, V>(x: Option<Box<Node<K, V>>>, key: &K) -> Option<Box<Node<K, V>>> {
-    if let Some(mut x) = x {
-        let next = x.next.take();
-        if x.key == *key {
-            next // this will drop x
-        } else {
-            x.next = delete(next, key);
-            Some(x)
+/// Unlink the node holding `key` from the chain starting at `link`.
+fn unlink<K: PartialEq, V>(mut link: &mut Option<Box<Node<K, V>>>, key: &K) -> Option<Box<Node<K, V>>> {
+    loop {
+        match *link {
+            Some(ref x) if x.key != *key => link = &mut link.as_mut().unwrap().next,
+            _ => break,
         }
-    } else {
-        None
     }
+    let mut x = link.take()?;
+    *link = x.next.take();
+    Some(x)
 }
 
-const M: usize = 97;
+/// Fewest buckets a table ever has.
+const INIT_CAPACITY: usize = 16;
 
-// separate chaining
-pub struct HashST<K, V> {
+/// Default average chain length above which the table doubles.
+pub const DEFAULT_MAX_LOAD: f64 = 2.0;
+
+/// Default average chain length below which the table halves.
+pub const DEFAULT_MIN_LOAD: f64 = 0.25;
+
+/// Separate chaining, hashing keys with `S`.
+///
+/// The default `RandomState` is SipHash keyed with random seeds, so that keys chosen to collide in one table
+/// do not collide in another. A faster hasher may be plugged in where the keys are trusted.
+pub struct HashST<K, V, S = RandomState> {
     st: Vec<Option<Box<Node<K, V>>>>,
+    n: usize,
+    min_load: f64,
+    max_load: f64,
+    hash_builder: S,
+}
+
+impl<K: Hash + PartialEq, V> HashST<K, V, RandomState> {
+    pub fn new() -> HashST<K, V, RandomState> {
+        HashST::with_hasher(RandomState::new())
+    }
+
+    /// An empty table with room for `capacity` keys before it has to grow.
+    pub fn with_capacity(capacity: usize) -> HashST<K, V, RandomState> {
+        HashST::with_capacity_and_hasher(capacity, RandomState::new())
+    }
 }
 
-impl<K: Hash + PartialEq, V> HashST<K, V> {
-    pub fn new() -> HashST<K, V> {
-        let mut st = Vec::with_capacity(M);
-        (0..M).map(|_| st.push(None)).count();
-        HashST { st: st }
+impl<K: Hash + PartialEq, V, S: BuildHasher> HashST<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> HashST<K, V, S> {
+        let mut st = Vec::with_capacity(INIT_CAPACITY);
+        (0..INIT_CAPACITY).map(|_| st.push(None)).count();
+        HashST {
+            st: st,
+            n: 0,
+            min_load: DEFAULT_MIN_LOAD,
+            max_load: DEFAULT_MAX_LOAD,
+            hash_builder: hash_builder,
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HashST<K, V, S> {
+        let mut st = HashST::with_hasher(hash_builder);
+        st.reserve(capacity);
+        st
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Halve the table when the average chain length drops below `min_load`, double it when the average exceeds
+    /// `max_load`.
+    ///
+    /// `max_load` must be more than twice `min_load`, so that a table just resized is not about to resize back.
+    pub fn set_load_factors(&mut self, min_load: f64, max_load: f64) {
+        assert!(min_load >= 0.0 && max_load > 2.0 * min_load, "invalid load factors");
+        self.min_load = min_load;
+        self.max_load = max_load;
+        if self.overloaded() || self.underloaded() {
+            let m = self.buckets_for(self.n);
+            self.resize(m);
+        }
+    }
+
+    /// Number of keys the table holds before it has to grow.
+    pub fn capacity(&self) -> usize {
+        (self.max_load * self.st.len() as f64) as usize
+    }
+
+    /// Make room for at least `additional` more keys without growing.
+    pub fn reserve(&mut self, additional: usize) {
+        let m = self.buckets_for(self.n + additional);
+        if m > self.st.len() {
+            self.resize(m);
+        }
+    }
+
+    /// Shrink the table to the fewest buckets holding the current keys.
+    pub fn shrink_to_fit(&mut self) {
+        let m = self.buckets_for(self.n);
+        if m < self.st.len() {
+            self.resize(m);
+        }
+    }
+
+    fn overloaded(&self) -> bool {
+        self.n as f64 > self.max_load * self.st.len() as f64
     }
 
-    // FIXME: hash state bug
-    fn hash(key: &K) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish() as usize % M
+    fn underloaded(&self) -> bool {
+        self.st.len() > INIT_CAPACITY && (self.n as f64) < self.min_load * self.st.len() as f64
+    }
+
+    /// Fewest buckets, a power of two, for `n` keys within the maximum load.
+    fn buckets_for(&self, n: usize) -> usize {
+        let mut m = INIT_CAPACITY;
+        while n as f64 > self.max_load * m as f64 {
+            m *= 2;
+        }
+        m
+    }
+
+    /// Rehash every key into `m` buckets, moving the nodes rather than allocating new ones.
+    fn resize(&mut self, m: usize) {
+        let mut st = Vec::with_capacity(m);
+        (0..m).map(|_| st.push(None)).count();
+        let old = mem::replace(&mut self.st, st);
+        for mut chain in old {
+            while let Some(mut x) = chain {
+                chain = x.next.take();
+                let i = self.hash(&x.key);
+                x.next = self.st[i].take();
+                self.st[i] = Some(x);
+            }
+        }
+    }
+
+    fn hash(&self, key: &K) -> usize {
+        self.bucket(self.hash_builder.hash_one(key))
+    }
+
+    fn bucket(&self, hash: u64) -> usize {
+        hash as usize % self.st.len()
     }
 
     pub fn get<T: Borrow<K>>(&self, key: T) -> Option<&V> {
         let key = key.borrow();
-        let i = Self::hash(key);
+        let i = self.hash(key);
         let mut x = self.st[i].as_ref();
         while x.is_some() {
             if *key == x.unwrap().key {
@@ -68,7 +199,7 @@ impl<K: Hash + PartialEq, V> HashST<K, V> {
 
     pub fn get_mut<T: Borrow<K>>(&mut self, key: T) -> Option<&mut V> {
         let key = key.borrow();
-        let i = Self::hash(key);
+        let i = self.hash(key);
         let mut x = self.st[i].as_mut();
         while x.is_some() {
             if x.as_ref().map_or(false, |n| n.key == *key) {
@@ -79,49 +210,504 @@ impl<K: Hash + PartialEq, V> HashST<K, V> {
         None
     }
 
-    pub fn put(&mut self, key: K, val: V) {
-        let i = Self::hash(&key);
-        {
-            let mut x = self.st[i].as_mut();
-            while x.is_some() {
-                if x.as_ref().map_or(false, |x| x.key == key) {
-                    x.unwrap().val = val;
-                    return;
-                } else {
-                    x = x.unwrap().next.as_mut();
-                }
+    pub fn contains_key<T: Borrow<K>>(&self, key: T) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Put `val` under `key`, returning the value it replaces, if any.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut e) => Some(e.insert(val)),
+            Entry::Vacant(e) => {
+                e.insert(val);
+                None
             }
         }
+    }
+
+    /// Push a key known not to be in the table onto the front of its chain, growing the table first if the key
+    /// would overload it. `hash` is the full hash of the key, still good for finding its bucket after growing.
+    fn insert_new(&mut self, hash: u64, key: K, val: V) -> &mut V {
+        self.n += 1;
+        if self.overloaded() {
+            let m = 2 * self.st.len();
+            self.resize(m);
+        }
+        let i = self.bucket(hash);
         let old = self.st[i].take();
         self.st[i] = Some(Box::new(Node {
             key: key,
             val: val,
             next: old,
-        }))
+        }));
+        &mut self.st[i].as_mut().unwrap().val
+    }
+
+    /// Like `insert`, dropping the value replaced.
+    pub fn put(&mut self, key: K, val: V) {
+        self.insert(key, val);
     }
 
+    /// Take `key` out of the table, returning its value if it was there.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.hash(key);
+        let x = unlink(&mut self.st[i], key)?;
+        self.n -= 1;
+        if self.underloaded() {
+            let m = self.st.len() / 2;
+            self.resize(m);
+        }
+        Some(x.val)
+    }
+
+    /// Like `remove`, dropping the value removed.
     pub fn delete(&mut self, key: &K) {
-        let i = Self::hash(key);
-        self.st[i] = delete(self.st[i].take(), key);
+        self.remove(key);
+    }
+
+    /// The place for `key` in the table, for updating its value or inserting one without hashing the key again.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        let hash = self.hash_builder.hash_one(&key);
+        let i = self.bucket(hash);
+        // find how far down the chain the key is before borrowing its value, since a borrow returned from inside
+        // the search would still hold `self` when the key is not found
+        let mut depth = 0;
+        let mut x = self.st[i].as_deref();
+        while let Some(node) = x {
+            if node.key == key {
+                break;
+            }
+            depth += 1;
+            x = node.next.as_deref();
+        }
+        if x.is_none() {
+            return Entry::Vacant(VacantEntry {
+                st: self,
+                hash: hash,
+                key: key,
+            });
+        }
+        let mut node = self.st[i].as_deref_mut().unwrap();
+        for _ in 0..depth {
+            node = node.next.as_deref_mut().unwrap();
+        }
+        Entry::Occupied(OccupiedEntry {
+            key: key,
+            val: &mut node.val,
+        })
     }
 
     pub fn size(&self) -> usize {
-        self.st
-            .iter()
-            .filter(|n| n.is_some())
-            .map(|h| h.as_ref().unwrap().size())
-            .sum()
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl<K, V, S> HashST<K, V, S> {
+    /// Iterate over the keys and values, in no particular order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            chains: self.st.iter(),
+            node: None,
+            nitem: self.n,
+        }
+    }
+
+    /// Iterate over the keys and values, in no particular order, with the values mutable.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            chains: self.st.iter_mut(),
+            node: None,
+            nitem: self.n,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Take every key and value out of the table. The table keeps its buckets.
+    pub fn drain(&mut self) -> IntoIter<K, V> {
+        let m = self.st.len();
+        let st = mem::replace(&mut self.st, (0..m).map(|_| None).collect());
+        IntoIter {
+            chains: st.into_iter(),
+            node: None,
+            nitem: mem::replace(&mut self.n, 0),
+        }
+    }
+
+    /// Remove every key. The table keeps its buckets.
+    pub fn clear(&mut self) {
+        self.drain();
     }
 }
 
-// TODO: how to implement IndexMut?
-impl<K: Hash + PartialEq, V> Index<K> for HashST<K, V> {
+impl<K: Hash + PartialEq, V, S: BuildHasher> Index<K> for HashST<K, V, S> {
     type Output = V;
     fn index(&self, index: K) -> &V {
         self.get(index).expect("key not exists")
     }
 }
 
+impl<K: Hash + PartialEq, V, S: BuildHasher> IndexMut<K> for HashST<K, V, S> {
+    fn index_mut(&mut self, index: K) -> &mut V {
+        self.get_mut(index).expect("key not exists")
+    }
+}
+
+/// A key's place in a `HashST`, from `HashST::entry`.
+pub enum Entry<'a, K: 'a, V: 'a, S: 'a> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+/// A key in the table, with its value.
+pub struct OccupiedEntry<'a, K, V: 'a> {
+    key: K,
+    val: &'a mut V,
+}
+
+/// A key not in the table, with the table to insert it into.
+pub struct VacantEntry<'a, K: 'a, V: 'a, S: 'a> {
+    st: &'a mut HashST<K, V, S>,
+    hash: u64,
+    key: K,
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> Entry<'a, K, V, S> {
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref e) => e.key(),
+            Entry::Vacant(ref e) => e.key(),
+        }
+    }
+
+    /// The key's value, inserting `default` first if the key is not in the table.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// The key's value, inserting the result of `default` first if the key is not in the table.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Apply `f` to the key's value if the key is in the table.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V: Default, S: BuildHasher> Entry<'a, K, V, S> {
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.val
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.val
+    }
+
+    /// The value, borrowed for as long as the table was.
+    pub fn into_mut(self) -> &'a mut V {
+        self.val
+    }
+
+    /// Replace the value, returning the old one.
+    pub fn insert(&mut self, val: V) -> V {
+        mem::replace(self.val, val)
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> VacantEntry<'a, K, V, S> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert the key with `val`, returning the value in the table.
+    pub fn insert(self, val: V) -> &'a mut V {
+        self.st.insert_new(self.hash, self.key, val)
+    }
+}
+
+pub struct Iter<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    chains: slice::Iter<'a, Option<Box<Node<K, V>>>>,
+    node: Option<&'a Node<K, V>>,
+    nitem: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            if let Some(x) = self.node {
+                self.node = x.next.as_deref();
+                self.nitem -= 1;
+                return Some((&x.key, &x.val));
+            }
+            self.node = self.chains.next()?.as_deref();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nitem, Some(self.nitem))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
+    fn len(&self) -> usize {
+        self.nitem
+    }
+}
+
+pub struct IterMut<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    chains: slice::IterMut<'a, Option<Box<Node<K, V>>>>,
+    node: Option<&'a mut Node<K, V>>,
+    nitem: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        loop {
+            if let Some(x) = self.node.take() {
+                self.node = x.next.as_deref_mut();
+                self.nitem -= 1;
+                return Some((&x.key, &mut x.val));
+            }
+            self.node = self.chains.next()?.as_deref_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nitem, Some(self.nitem))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
+    fn len(&self) -> usize {
+        self.nitem
+    }
+}
+
+pub struct IntoIter<K, V> {
+    chains: vec::IntoIter<Option<Box<Node<K, V>>>>,
+    node: Option<Box<Node<K, V>>>,
+    nitem: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        loop {
+            if let Some(x) = self.node.take() {
+                let Node { key, val, next } = *x;
+                self.node = next;
+                self.nitem -= 1;
+                return Some((key, val));
+            }
+            self.node = self.chains.next()?;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nitem, Some(self.nitem))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.nitem
+    }
+}
+
+pub struct Keys<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+pub struct Values<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<K, V, S> IntoIterator for HashST<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            chains: self.st.into_iter(),
+            node: None,
+            nitem: self.n,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashST<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashST<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> Default for HashST<K, V, S> {
+    fn default() -> Self {
+        HashST::with_hasher(S::default())
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> FromIterator<(K, V)> for HashST<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut st = HashST::default();
+        st.extend(iter);
+        st
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Extend<(K, V)> for HashST<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.insert(key, val);
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for HashST<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Clone, V: Clone, S: Clone> Clone for HashST<K, V, S> {
+    fn clone(&self) -> Self {
+        HashST {
+            st: self.st.clone(),
+            n: self.n,
+            min_load: self.min_load,
+            max_load: self.max_load,
+            hash_builder: self.hash_builder.clone(),
+        }
+    }
+}
+
+/// Tables are equal when they hold the same keys with equal values, whatever their buckets.
+impl<K: Hash + PartialEq, V: PartialEq, S: BuildHasher> PartialEq for HashST<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for HashST<K, V, S> {}
+
+#[cfg(test)]
+fn longest_chain<K, V, S>(m: &HashST<K, V, S>) -> usize {
+    let chain_len = |c: &Option<Box<Node<K, V>>>| {
+        let mut len = 0;
+        let mut x = c.as_ref();
+        while let Some(node) = x {
+            len += 1;
+            x = node.next.as_ref();
+        }
+        len
+    };
+    m.st.iter().map(chain_len).max().unwrap_or(0)
+}
+
 #[test]
 fn test_separate_chaining_hash_st() {
     let mut m = HashST::new();
@@ -143,3 +729,225 @@ fn test_separate_chaining_hash_st() {
 
     assert_eq!(m["Address"], "Shanghai");
 }
+
+#[test]
+fn test_hash_st_resize() {
+    let mut m = HashST::new();
+    assert_eq!(16, m.st.len());
+    for i in 0..1000 {
+        m.put(i, i * i);
+    }
+    m.put(7, 7);
+    assert_eq!(1000, m.size());
+    assert!(m.st.len() >= 500 && m.capacity() >= 1000);
+    assert!(longest_chain(&m) < 16);
+    assert!((0..1000).filter(|&i| i != 7).all(|i| m.get(i) == Some(&(i * i))));
+
+    for i in 0..990 {
+        m.delete(&i);
+    }
+    m.delete(&0);
+    assert_eq!(10, m.size());
+    assert!(m.st.len() <= 64);
+    assert!((990..1000).all(|i| m.get(i) == Some(&(i * i))));
+}
+
+#[test]
+fn test_hash_st_capacity() {
+    let mut m = HashST::with_capacity(1000);
+    let buckets = m.st.len();
+    assert!(m.capacity() >= 1000);
+    for i in 0..1000 {
+        m.put(i, ());
+    }
+    assert_eq!(buckets, m.st.len());
+
+    m.reserve(5000);
+    assert!(m.capacity() >= 6000);
+    m.shrink_to_fit();
+    assert_eq!(buckets, m.st.len());
+
+    m.set_load_factors(4.0, 16.0);
+    assert!(m.st.len() < buckets);
+    assert!((0..1000).all(|i| m.get(i).is_some()));
+    m.set_load_factors(0.0, 1.0);
+    assert!(m.st.len() >= 1000);
+
+    for i in 0..1000 {
+        m.delete(&i);
+    }
+    assert!(m.is_empty());
+    assert!(m.st.len() >= 1000);
+    m.shrink_to_fit();
+    assert_eq!(16, m.st.len());
+}
+
+/// Hashes nothing but the low byte of the input, as a stand-in for a hasher an attacker has figured out.
+#[cfg(test)]
+#[derive(Default)]
+struct WeakHasher(u64);
+
+#[cfg(test)]
+impl Hasher for WeakHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        if let Some(&b) = bytes.first() {
+            self.0 = b as u64;
+        }
+    }
+
+    // the first byte of an integer is its low byte only on little-endian targets
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i & 0xff;
+    }
+}
+
+#[test]
+fn test_hash_st_hasher() {
+    // multiples of 256 all collide under the weak hasher
+    let keys = (0..500u64).map(|i| i * 256).collect::<Vec<u64>>();
+    let mut weak = HashST::with_hasher(BuildHasherDefault::<WeakHasher>::default());
+    for &k in &keys {
+        weak.put(k, k);
+    }
+    assert_eq!(500, weak.size());
+    assert_eq!(500, longest_chain(&weak));
+    assert!(keys.iter().all(|&k| weak.get(k) == Some(&k)));
+    weak.delete(&256);
+    assert_eq!((499, None), (weak.size(), weak.get(256)));
+
+    // while SipHash keyed at random spreads them out
+    let mut strong = HashST::with_capacity(keys.len());
+    for &k in &keys {
+        strong.put(k, k);
+    }
+    assert!(longest_chain(&strong) < 16);
+
+    // and two tables seed differently, so no one set of keys collides in both
+    let (a, b) = (HashST::<u64, ()>::new(), HashST::<u64, ()>::new());
+    assert!(keys.iter().any(|k| a.hash(k) != b.hash(k)));
+
+    let mut fixed = HashST::with_capacity_and_hasher(10, BuildHasherDefault::<WeakHasher>::default());
+    fixed.put(3u64, "three");
+    assert_eq!(0, fixed.hasher().build_hasher().finish());
+    assert_eq!("three", fixed[3]);
+}
+
+#[test]
+fn test_hash_st_map_api() {
+    let mut m = HashST::new();
+    assert_eq!(None, m.insert("Name", "Feather"));
+    assert_eq!(Some("Feather"), m.insert("Name", "Quill"));
+    assert_eq!(None, m.insert("Age", "25"));
+    assert!(m.contains_key("Age"));
+    assert_eq!(Some("25"), m.remove(&"Age"));
+    assert_eq!(None, m.remove(&"Age"));
+    assert_eq!(1, m.size());
+
+    m["Name"] = "Feather";
+    assert_eq!("Feather", m["Name"]);
+
+    let mut counts = HashST::new();
+    for word in "the quick brown fox jumps over the lazy dog the end".split(' ') {
+        *counts.entry(word).or_insert_with(|| 0) += 1;
+    }
+    assert_eq!((9, 3, 1), (counts.size(), counts["the"], counts["fox"]));
+    assert_eq!("dog", *counts.entry("dog").key());
+    *counts.entry("dog").and_modify(|n| *n += 10).or_default() += 1;
+    *counts.entry("cat").and_modify(|n| *n += 10).or_default() += 1;
+    assert_eq!((12, 1), (counts["dog"], counts["cat"]));
+    match counts.entry("end") {
+        Entry::Occupied(mut e) => assert_eq!(1, e.insert(5)),
+        Entry::Vacant(_) => panic!("end is in the table"),
+    }
+    assert_eq!(5, counts["end"]);
+
+    // an entry hashes its key once, whether it is in the table or not
+    let hashes = Rc::new(Cell::new(0));
+    let mut m = HashST::with_hasher(CountingState(hashes.clone()));
+    *m.entry("one").or_insert(0) += 1;
+    *m.entry("one").or_insert(0) += 1;
+    assert_eq!(None, m.insert("two", 2));
+    assert_eq!(Some(2), m.insert("two", 3));
+    assert_eq!((4, 2, 3), (hashes.get(), m["one"], m["two"]));
+}
+
+/// Counts the keys hashed.
+#[cfg(test)]
+struct CountingState(Rc<Cell<usize>>);
+
+#[cfg(test)]
+impl BuildHasher for CountingState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        self.0.set(self.0.get() + 1);
+        DefaultHasher::new()
+    }
+}
+
+#[test]
+fn test_hash_st_iter() {
+    let mut m = (0..100).map(|i| (i, i * i)).collect::<HashST<u32, u32>>();
+    assert_eq!(100, m.iter().len());
+    let mut keys = m.keys().cloned().collect::<Vec<u32>>();
+    keys.sort();
+    assert_eq!((0..100).collect::<Vec<u32>>(), keys);
+    assert_eq!((0..100).map(|i| i * i).sum::<u32>(), m.values().sum());
+
+    for (k, v) in m.iter_mut() {
+        *v -= k;
+    }
+    for (k, v) in &mut m {
+        *v += k;
+    }
+    assert!((&m).into_iter().all(|(&k, &v)| v == k * k));
+
+    let copy = m.clone();
+    m.extend((100..200).map(|i| (i, i * i)));
+    assert!(m != copy);
+    for i in 100..200 {
+        m.delete(&i);
+    }
+    assert!(m == copy);
+    m.insert(0, 1);
+    assert!(m != copy);
+
+    let mut pairs = m.drain().collect::<Vec<(u32, u32)>>();
+    pairs.sort();
+    assert_eq!((0, 1), pairs[0]);
+    assert_eq!(100, pairs.len());
+    assert!(m.is_empty() && m.iter().next().is_none());
+
+    let one = vec![("one", 1)].into_iter().collect::<HashST<&str, i32>>();
+    assert_eq!("{\"one\": 1}", format!("{:?}", one));
+    assert_eq!(vec![("one", 1)], one.into_iter().collect::<Vec<(&str, i32)>>());
+}
+
+#[test]
+fn quicktest_hash_st() {
+    fn prop(ops: Vec<(bool, u8, u16)>) -> bool {
+        let mut m = HashST::with_hasher(BuildHasherDefault::<WeakHasher>::default());
+        let mut expected = HashMap::new();
+        for (insert, key, val) in ops {
+            let key = key as u64 * 64;
+            let ok = if insert {
+                m.insert(key, val) == expected.insert(key, val)
+            } else {
+                m.remove(&key) == expected.remove(&key)
+            };
+            if !ok {
+                return false;
+            }
+        }
+        let mut pairs = m.iter().map(|(&k, &v)| (k, v)).collect::<Vec<(u64, u16)>>();
+        let mut expected = expected.into_iter().collect::<Vec<(u64, u16)>>();
+        pairs.sort();
+        expected.sort();
+        m.size() == expected.len() && pairs == expected
+    }
+    quickcheck(prop as fn(Vec<(bool, u8, u16)>) -> bool);
+}
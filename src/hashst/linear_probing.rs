@@ -0,0 +1,293 @@
+use super::INIT_CAPACITY;
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+use std::ops::Index;
+
+#[cfg(test)]
+use super::WeakHasher;
+#[cfg(test)]
+use quickcheck::quickcheck;
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use std::hash::BuildHasherDefault;
+
+/// Share of the slots in use above which the table doubles.
+const MAX_LOAD: f64 = 0.75;
+
+/// Share of the slots in use below which the table halves.
+const MIN_LOAD: f64 = 0.125;
+
+struct Slot<K, V> {
+    hash: u64,
+    key: K,
+    val: V,
+}
+
+/// Open addressing with linear probing, keys and values stored inline in one array.
+///
+/// Insertion follows the Robin Hood rule: a key probing past a key nearer to its home slot takes that slot and
+/// carries on inserting the displaced key, which keeps probe sequences short and even. Deletion shifts the keys
+/// after the removed one back instead of leaving tombstones.
+pub struct LinearProbingHashST<K, V, S = RandomState> {
+    slots: Vec<Option<Slot<K, V>>>,
+    n: usize,
+    hash_builder: S,
+}
+
+impl<K: Hash + PartialEq, V> LinearProbingHashST<K, V, RandomState> {
+    pub fn new() -> LinearProbingHashST<K, V, RandomState> {
+        LinearProbingHashST::with_hasher(RandomState::new())
+    }
+
+    /// An empty table with room for `capacity` keys before it has to grow.
+    pub fn with_capacity(capacity: usize) -> LinearProbingHashST<K, V, RandomState> {
+        LinearProbingHashST::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> LinearProbingHashST<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> LinearProbingHashST<K, V, S> {
+        LinearProbingHashST::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> LinearProbingHashST<K, V, S> {
+        let mut m = INIT_CAPACITY;
+        while capacity as f64 > MAX_LOAD * m as f64 {
+            m *= 2;
+        }
+        LinearProbingHashST {
+            slots: (0..m).map(|_| None).collect(),
+            n: 0,
+            hash_builder: hash_builder,
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Number of keys the table holds before it has to grow.
+    pub fn capacity(&self) -> usize {
+        (MAX_LOAD * self.slots.len() as f64) as usize
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    /// How far the key with hash `hash` sits from its home slot when found at `i`.
+    fn displacement(&self, hash: u64, i: usize) -> usize {
+        i.wrapping_sub(hash as usize) & self.mask()
+    }
+
+    /// Slot holding `key`. The search stops early at a key nearer to its home than `key` would be, since Robin
+    /// Hood insertion would have put `key` there.
+    fn find(&self, key: &K) -> Option<usize> {
+        let hash = self.hash_builder.hash_one(key);
+        let mut i = hash as usize & self.mask();
+        let mut dist = 0;
+        while let Some(ref slot) = self.slots[i] {
+            if self.displacement(slot.hash, i) < dist {
+                return None;
+            }
+            if slot.hash == hash && slot.key == *key {
+                return Some(i);
+            }
+            i = (i + 1) & self.mask();
+            dist += 1;
+        }
+        None
+    }
+
+    /// Place a key known not to be in the table yet.
+    fn insert_new(&mut self, mut slot: Slot<K, V>) {
+        let mut i = slot.hash as usize & self.mask();
+        let mut dist = 0;
+        loop {
+            let resident = match self.slots[i] {
+                Some(ref resident) => self.displacement(resident.hash, i),
+                None => {
+                    self.slots[i] = Some(slot);
+                    return;
+                }
+            };
+            if resident < dist {
+                mem::swap(self.slots[i].as_mut().unwrap(), &mut slot);
+                dist = resident;
+            }
+            i = (i + 1) & self.mask();
+            dist += 1;
+        }
+    }
+
+    fn resize(&mut self, m: usize) {
+        let old = mem::replace(&mut self.slots, (0..m).map(|_| None).collect());
+        for slot in old.into_iter().flatten() {
+            self.insert_new(slot);
+        }
+    }
+
+    pub fn get<T: Borrow<K>>(&self, key: T) -> Option<&V> {
+        let i = self.find(key.borrow())?;
+        self.slots[i].as_ref().map(|slot| &slot.val)
+    }
+
+    pub fn get_mut<T: Borrow<K>>(&mut self, key: T) -> Option<&mut V> {
+        let i = self.find(key.borrow())?;
+        self.slots[i].as_mut().map(|slot| &mut slot.val)
+    }
+
+    pub fn put(&mut self, key: K, val: V) {
+        if let Some(i) = self.find(&key) {
+            self.slots[i].as_mut().unwrap().val = val;
+            return;
+        }
+        if (self.n + 1) as f64 > MAX_LOAD * self.slots.len() as f64 {
+            let m = 2 * self.slots.len();
+            self.resize(m);
+        }
+        let hash = self.hash_builder.hash_one(&key);
+        self.insert_new(Slot {
+            hash: hash,
+            key: key,
+            val: val,
+        });
+        self.n += 1;
+    }
+
+    pub fn delete(&mut self, key: &K) {
+        let mut i = match self.find(key) {
+            Some(i) => i,
+            None => return,
+        };
+        self.slots[i] = None;
+        self.n -= 1;
+        // shift back the keys after the hole, up to an empty slot or a key at its home
+        loop {
+            let j = (i + 1) & self.mask();
+            match self.slots[j] {
+                Some(ref slot) if self.displacement(slot.hash, j) > 0 => (),
+                _ => break,
+            }
+            self.slots.swap(i, j);
+            i = j;
+        }
+        let m = self.slots.len();
+        if m > INIT_CAPACITY && (self.n as f64) < MIN_LOAD * m as f64 {
+            self.resize(m / 2);
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> Default for LinearProbingHashST<K, V, S> {
+    fn default() -> Self {
+        LinearProbingHashST::with_hasher(S::default())
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Index<K> for LinearProbingHashST<K, V, S> {
+    type Output = V;
+    fn index(&self, index: K) -> &V {
+        self.get(index).expect("key not exists")
+    }
+}
+
+#[cfg(test)]
+fn longest_probe<K, V, S: BuildHasher>(m: &LinearProbingHashST<K, V, S>) -> usize
+where
+    K: Hash + PartialEq,
+{
+    (0..m.slots.len())
+        .filter_map(|i| m.slots[i].as_ref().map(|slot| m.displacement(slot.hash, i)))
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_linear_probing_hash_st() {
+    let mut m = LinearProbingHashST::new();
+    assert_eq!(m.size(), 0);
+    m.put("Name", "Feather");
+    m.put("Age", "25");
+    m.put("Address", "Beijing");
+
+    assert_eq!(m.size(), 3);
+    assert_eq!(m.get("Age"), Some(&"25"));
+    assert_eq!(m.get("Gender"), None);
+
+    m.delete(&"Age");
+    m.delete(&"Gender");
+    assert_eq!(m.size(), 2);
+    assert_eq!(m.get("Age"), None);
+
+    m.get_mut("Address").map(|v| *v = "Shanghai");
+    assert_eq!(m.get("Address"), Some(&"Shanghai"));
+    m.put("Name", "Quill");
+    assert_eq!((2, "Quill"), (m.size(), m["Name"]));
+
+    let mut m: LinearProbingHashST<u32, u32> = Default::default();
+    m.put(1, 1);
+    assert_eq!((1, Some(&1)), (m.size(), m.get(1)));
+
+    let mut m = LinearProbingHashST::with_capacity(1000);
+    let slots = m.slots.len();
+    assert!(m.capacity() >= 1000);
+    for i in 0..1000 {
+        m.put(i, i * i);
+    }
+    assert_eq!(slots, m.slots.len());
+    assert!(longest_probe(&m) < 32);
+    for i in 0..990 {
+        m.delete(&i);
+    }
+    assert!(m.slots.len() < 128);
+    assert!((990..1000).all(|i| m.get(i) == Some(&(i * i))));
+}
+
+#[test]
+fn test_linear_probing_collisions() {
+    // every multiple of 256 has the same home slot, so deletions have long runs to shift back
+    let mut m = LinearProbingHashST::with_hasher(BuildHasherDefault::<WeakHasher>::default());
+    for i in 0..100u64 {
+        m.put(i * 256, i);
+        m.put(i * 256 + 1, i);
+    }
+    assert_eq!(200, m.size());
+    for i in (0..100u64).filter(|i| i % 3 == 0) {
+        m.delete(&(i * 256));
+    }
+    for i in 0..100u64 {
+        assert_eq!(if i % 3 == 0 { None } else { Some(&i) }, m.get(i * 256));
+        assert_eq!(Some(&i), m.get(i * 256 + 1));
+    }
+}
+
+#[test]
+fn quicktest_linear_probing_hash_st() {
+    fn prop(ops: Vec<(bool, u8, u16)>) -> bool {
+        let mut m = LinearProbingHashST::with_hasher(BuildHasherDefault::<WeakHasher>::default());
+        let mut expected = HashMap::new();
+        for (insert, key, val) in ops {
+            if insert {
+                m.put(key as u64 * 64, val);
+                expected.insert(key as u64 * 64, val);
+            } else {
+                m.delete(&(key as u64 * 64));
+                expected.remove(&(key as u64 * 64));
+            }
+        }
+        m.size() == expected.len() && (0..256u64).all(|k| m.get(k * 64) == expected.get(&(k * 64)))
+    }
+    quickcheck(prop as fn(Vec<(bool, u8, u16)>) -> bool);
+}
@@ -6,6 +6,7 @@
 pub mod bag;
 pub mod deque;
 pub mod graph;
+pub mod hashset;
 pub mod hashst;
 pub mod priority_queue;
 pub mod queue;
@@ -10,6 +10,10 @@ pub use super::graph::Graph;
 
 pub use super::graph::Digraph;
 
+pub use super::graph::EdgeWeightedGraph;
+
+pub use super::graph::EdgeWeightedDigraph;
+
 pub use super::priority_queue::{IndexMinPQ, MaxPQ, MinPQ};
 
 pub use super::tries::TernarySearchTrie;
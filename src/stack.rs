@@ -61,6 +61,16 @@ impl<T> Stack<T> {
     }
 }
 
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        // unlink nodes one by one, the default recursive drop overflows on long stacks
+        let mut p = self.s.take();
+        while let Some(mut node) = p {
+            p = node.next.take();
+        }
+    }
+}
+
 pub struct IntoIter<T> {
     stack: Stack<T>,
 }
@@ -195,3 +205,12 @@ fn test_stack_clone() {
     assert_eq!(s.len(), 2);
     assert_eq!(t.len(), 3);
 }
+
+#[test]
+fn test_stack_drop_long() {
+    let mut s = Stack::new();
+    for i in 0..1_000_000 {
+        s.push(i);
+    }
+    drop(s);
+}